@@ -0,0 +1,100 @@
+//! Throughput benchmark of the text database parsing.
+//!
+//! Generates a synthetic text database of `LEDGERSTATS_BENCH_MB` megabytes(256 by default)
+//! and compares the sequential and the memory-mapped parallel parsing, e.g.
+//! `LEDGERSTATS_BENCH_MB=4096 cargo bench --bench parse` for a multi-GB input.
+
+#![deny(unsafe_code)]
+
+use std::fmt::Write as _;
+use std::io::{Seek, SeekFrom, Write};
+use std::time::Instant;
+
+use ledgerstats::ledger;
+
+/// Writes a synthetic database of about the provided size and returns its path.
+/// The database is written under a temporary name and renamed when complete,
+/// so an interrupted run never leaves a truncated database behind.
+fn generate_database(megabytes: usize) -> std::io::Result<std::path::PathBuf> {
+    let path = std::env::temp_dir().join(format!("ledgerstats-bench-{megabytes}mb.txt"));
+
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let partial_path = path.with_extension(format!("{}.partial", std::process::id()));
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&partial_path)?);
+
+    // The transactions number is not known yet, the zero-padded placeholder is patched at the end
+    let header = format!("{:020}\n", 0);
+    file.write_all(header.as_bytes())?;
+
+    let mut bytes = header.len();
+    let mut transactions = 0usize;
+    let mut line = String::new();
+
+    while bytes < megabytes << 20 {
+        let id = transactions + 2;
+        let left = 1 + (id * 7919) % (id - 1);
+        let right = 1 + (id * 104_729) % (id - 1);
+
+        line.clear();
+        writeln!(line, "{left} {right} {}", id / 10).expect("the line must be formattable");
+        file.write_all(line.as_bytes())?;
+
+        bytes += line.len();
+        transactions += 1;
+    }
+
+    let mut file = file.into_inner().map_err(|e| e.into_error())?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{transactions:020}")?;
+    file.sync_all()?;
+
+    std::fs::rename(&partial_path, &path)?;
+
+    Ok(path)
+}
+
+/// Runs the parser and prints its throughput.
+fn measure(name: &str, bytes: usize, parse: impl FnOnce() -> usize) {
+    let start = Instant::now();
+    let transactions = parse();
+    let elapsed = start.elapsed().as_secs_f64();
+
+    println!(
+        "{name}: {transactions} txs in {elapsed:.3}s, {:.1} MB/s",
+        bytes as f64 / elapsed / (1 << 20) as f64
+    );
+}
+
+#[allow(unsafe_code)]
+fn main() -> std::io::Result<()> {
+    let megabytes = std::env::var("LEDGERSTATS_BENCH_MB")
+        .ok()
+        .and_then(|mb| mb.parse().ok())
+        .unwrap_or(256);
+
+    let path = generate_database(megabytes)?;
+    let bytes = std::fs::metadata(&path)?.len() as usize;
+
+    measure("sequential", bytes, || {
+        let database = std::fs::read_to_string(&path).expect("the database must be readable");
+
+        ledger::read_txs_from_db(&database)
+            .expect("the database must be valid")
+            .len()
+    });
+
+    measure("parallel", bytes, || {
+        let file = std::fs::File::open(&path).expect("the database must be readable");
+        // SAFETY: the benchmark database is not modified while mapped.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.expect("the database must be mappable");
+
+        ledger::par_read_txs_from_db(&mmap)
+            .expect("the database must be valid")
+            .len()
+    });
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+
+use ledgerstats::ledger::AuditConfig;
+
+/// Detects the lazy approvals and the parasite chains and prints their scores.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let config = AuditConfig {
+        lazy_gap: value_t!(matches, "LAZY_GAP", u64)?,
+        min_chain_size: value_t!(matches, "MIN_CHAIN_SIZE", usize)?,
+    };
+
+    if config.lazy_gap == 0 {
+        bail!("the lazy gap must be positive");
+    }
+
+    let report = ledger.audit(&config);
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("------- Parasite chains --------");
+
+    for chain in &report.parasite_chains {
+        println!(
+            "{} - {} {} {:.2}",
+            ledger.display_id(chain.root),
+            chain.gap,
+            chain.transactions.len(),
+            chain.score
+        );
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("LAZY GAP: {}", config.lazy_gap);
+    println!("LAZY APPROVALS: {}", report.lazy_approvals.len());
+    println!("AVG LAZY GAP: {}", report.lazy_gaps.mean);
+    println!("MAX LAZY GAP: {}", report.lazy_gaps.max);
+    println!("---");
+    println!("PARASITE CHAINS: {}", report.parasite_chains.len());
+    println!(
+        "PARASITE TXS: {}",
+        report
+            .parasite_chains
+            .iter()
+            .map(|chain| chain.transactions.len())
+            .sum::<usize>()
+    );
+    println!("SCORE: {:.2}", report.score);
+    println!("--------------------------------");
+
+    Ok(())
+}
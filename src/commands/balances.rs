@@ -0,0 +1,41 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Prints the address balances after applying the value transfers and the supply statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let top = value_t!(matches, "TOP", usize)?;
+
+    let balances = ledger.balances();
+
+    let mut richest = balances
+        .balances
+        .iter()
+        .filter(|(_, amount)| **amount > 0)
+        .collect::<Vec<_>>();
+    richest.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("----------- Balances -----------");
+
+    for (address, amount) in richest.into_iter().take(top) {
+        println!("{address} {amount}");
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("TOTAL SUPPLY: {}", balances.total_supply);
+    println!("ADDRESSES: {}", balances.balances.len());
+    println!("ACTIVE ADDRESSES: {}", balances.active_addresses);
+    println!("APPLIED TXS: {}", balances.applied);
+    println!("REJECTED TXS: {}", balances.rejected.len());
+    println!("---");
+    println!("AVG BALANCE: {}", balances.distribution.mean);
+    println!("MEDIAN BALANCE: {}", balances.distribution.median);
+    println!("P90 BALANCE: {}", balances.distribution.p90);
+    println!("MAX BALANCE: {}", balances.distribution.max);
+    println!("--------------------------------");
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger::ConfirmationRule;
+
+/// The cumulative weight confirming a transaction if no rule is provided.
+const DEFAULT_CONFIRMATION_WEIGHT: u64 = 10;
+
+/// Replays the database and prints the confirmation latency statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let (rule, report) = if matches.is_present("MILESTONES") {
+        ("milestones".to_owned(), ledger.milestone_confirmations())
+    } else {
+        let rule = confirmation_rule(matches)?;
+
+        (rule.to_string(), ledger.confirmations(rule))
+    };
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("--------- Confirmation ---------");
+    println!("RULE: {rule}");
+    println!("CONFIRMED TXS: {}", report.confirmations.len());
+    println!("NEVER CONFIRMED TXS: {}", report.unconfirmed);
+    println!("---");
+    println!("MIN LATENCY: {}", report.latencies.min);
+    println!("AVG LATENCY: {}", report.latencies.mean);
+    println!("MEDIAN LATENCY: {}", report.latencies.median);
+    println!("P90 LATENCY: {}", report.latencies.p90);
+    println!("P99 LATENCY: {}", report.latencies.p99);
+    println!("MAX LATENCY: {}", report.latencies.max);
+    println!("--------------------------------");
+
+    Ok(())
+}
+
+/// Reads the confirmation rule arguments.
+fn confirmation_rule(matches: &ArgMatches) -> Result<ConfirmationRule> {
+    let rule = if matches.is_present("TIPS") {
+        ConfirmationRule::ApprovingTips(value_t!(matches, "TIPS", usize)?)
+    } else if matches.is_present("WEIGHT") {
+        ConfirmationRule::CumulativeWeight(value_t!(matches, "WEIGHT", u64)?)
+    } else {
+        ConfirmationRule::CumulativeWeight(DEFAULT_CONFIRMATION_WEIGHT)
+    };
+
+    Ok(rule)
+}
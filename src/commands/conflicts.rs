@@ -0,0 +1,69 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use itertools::Itertools;
+
+use ledgerstats::ledger::ConflictResolution;
+
+/// The cumulative weight resolving a conflict if no rule is provided.
+const DEFAULT_RESOLUTION_WEIGHT: u64 = 10;
+
+/// Detects the double-spending conflicts and prints their resolution statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let resolution = if matches.is_present("MILESTONES") {
+        ConflictResolution::Milestones
+    } else if matches.is_present("WEIGHT") {
+        ConflictResolution::CumulativeWeight(value_t!(matches, "WEIGHT", u64)?)
+    } else {
+        ConflictResolution::CumulativeWeight(DEFAULT_RESOLUTION_WEIGHT)
+    };
+
+    let report = ledger.conflicts(resolution);
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("---------- Conflicts -----------");
+
+    for conflict in &report.conflicts {
+        let winner = conflict
+            .winner
+            .map_or_else(|| "-".to_owned(), |id| ledger.display_id(id));
+        let resolution_time = conflict
+            .resolution_time
+            .map_or_else(|| "-".to_owned(), |t| t.to_string());
+
+        println!(
+            "{} - {} {} {} {}",
+            conflict.address,
+            conflict
+                .transactions
+                .iter()
+                .map(|id| ledger.display_id(*id))
+                .join(","),
+            winner,
+            resolution_time,
+            conflict.orphaned.len()
+        );
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("RULE: {resolution}");
+    println!("CONFLICTS: {}", report.conflicts.len());
+    println!("RESOLVED CONFLICTS: {}", report.resolved);
+    println!(
+        "UNRESOLVED CONFLICTS: {}",
+        report.conflicts.len() - report.resolved
+    );
+    println!("---");
+    println!("AVG RESOLUTION TIME: {}", report.resolution_times.mean);
+    println!("MAX RESOLUTION TIME: {}", report.resolution_times.max);
+    println!("---");
+    println!("ORPHANED TXS: {}", report.orphaned.len());
+    println!("AVG ORPHANED CONE: {}", report.orphaned_cones.mean);
+    println!("MAX ORPHANED CONE: {}", report.orphaned_cones.max);
+    println!("--------------------------------");
+
+    Ok(())
+}
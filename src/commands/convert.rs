@@ -0,0 +1,12 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Converts the database from the input format to the output format.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let transactions = super::read_transactions(matches)?;
+    let database = super::write_transactions(matches, &transactions)?;
+
+    std::fs::write(value_t!(matches, "OUTPUT", String)?, database)?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Prints the transactions dominating the most transactions and the dominator tree statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let top = value_t!(matches, "TOP", usize)?;
+
+    let dominators = ledger.dominators();
+    let bottlenecks = dominators.bottlenecks();
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("--------- Bottlenecks ----------");
+
+    for (id, dominated) in bottlenecks.iter().take(top) {
+        let frontier = dominators.frontiers.get(id).map_or(0, |f| f.len());
+
+        println!("{} - {} {}", ledger.display_id(*id), dominated, frontier);
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("BOTTLENECKS: {}", bottlenecks.len());
+    println!("AVG DOMINATOR TREE DEPTH: {}", dominators.tree_depths.mean);
+    println!("MAX DOMINATOR TREE DEPTH: {}", dominators.tree_depths.max);
+    println!("---");
+    println!("AVG FRONTIER SIZE: {}", dominators.frontier_sizes.mean);
+    println!("MAX FRONTIER SIZE: {}", dominators.frontier_sizes.max);
+    println!("--------------------------------");
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger;
+
+/// Exports the computed fields of every transaction to the output file or stdout.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let fields = ledger::write_tx_fields_to_csv(&ledger, super::delimiter(matches)?)?;
+
+    match matches.value_of("OUTPUT") {
+        Some(output) => std::fs::write(output, fields)?,
+        None => print!("{fields}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::io::Write;
+
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+
+use ledgerstats::ledger::{self, GeneratorConfig, TipSelection};
+
+/// Generates a synthetic database and writes it to the output file or stdout.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let tip_selection = match matches.value_of("TIP_SELECTION") {
+        Some("walk") => TipSelection::RandomWalk {
+            alpha: value_t!(matches, "ALPHA", f64)?,
+        },
+        _ => TipSelection::Uniform,
+    };
+
+    let config = GeneratorConfig {
+        transactions: value_t!(matches, "TRANSACTIONS", usize)?,
+        arrival_rate: value_t!(matches, "RATE", f64)?,
+        simulation: super::simulation_config(matches, tip_selection)?,
+    };
+
+    if config.arrival_rate <= 0.0 {
+        bail!("the arrival rate must be positive");
+    }
+
+    let database = super::write_transactions(matches, &ledger::generate_txs(&config))?;
+
+    match matches.value_of("OUTPUT") {
+        Some(output) => std::fs::write(output, database)?,
+        None => std::io::stdout().write_all(&database)?,
+    }
+
+    Ok(())
+}
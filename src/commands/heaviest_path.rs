@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Prints the heaviest path from transaction 1 to a tip and how much of the tangle lies near it.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let path = ledger.heaviest_path();
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("-------- Heaviest path ---------");
+
+    for id in &path {
+        let timestamp = ledger
+            .transaction(*id)
+            .and_then(|info| info.timestamp)
+            .map_or_else(|| "-".to_owned(), |ts| ts.to_string());
+
+        println!("{} - {}", ledger.display_id(*id), timestamp);
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("LENGTH: {}", path.len() - 1);
+    println!("ONE HOP FRACTION: {}", ledger.one_hop_fraction(&path));
+    println!("--------------------------------");
+
+    Ok(())
+}
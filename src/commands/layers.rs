@@ -0,0 +1,42 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger::{self, Timestamp};
+
+/// Prints the per-depth layers in the output format.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let layers = ledger.layers();
+
+    match matches.value_of("OUTPUT_FORMAT") {
+        Some("csv") => print!(
+            "{}",
+            ledger::write_layers_to_csv(&layers, super::delimiter(matches)?)?
+        ),
+        Some("json") => println!("{}", serde_json::to_string_pretty(&layers)?),
+        _ => {
+            super::print_database(super::db_file_path(matches));
+
+            println!("------------ Layers ------------");
+
+            let timestamp =
+                |ts: Option<Timestamp>| ts.map_or_else(|| "-".to_owned(), |t| t.to_string());
+
+            for layer in &layers {
+                println!(
+                    "{} - {} {} {} {:.2} {}",
+                    layer.depth,
+                    layer.transactions,
+                    timestamp(layer.min_timestamp),
+                    timestamp(layer.max_timestamp),
+                    layer.avg_in_degree,
+                    layer.tips
+                );
+            }
+
+            println!("--------------------------------");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anyhow::{anyhow, bail, Result};
+use clap::ArgMatches;
+use itertools::Itertools;
+
+/// Prints the lowest common ancestors of the transactions.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let ids = matches
+        .values_of("TXS")
+        .into_iter()
+        .flatten()
+        .map(|id| {
+            ledger
+                .resolve_id(id)
+                .ok_or_else(|| anyhow!("unknown transaction: '{id}'"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if ids.len() < 2 {
+        bail!("at least 2 transactions are required");
+    }
+
+    let ancestors = ledger
+        .lowest_common_ancestors(&ids)
+        .ok_or_else(|| anyhow!("the transactions are not solid transactions of the ledger"))?;
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("------- Common ancestors -------");
+    println!(
+        "TXS: {}",
+        ids.iter().map(|id| ledger.display_id(*id)).join(" ")
+    );
+    println!("---");
+
+    for id in &ancestors {
+        let depth = ledger
+            .transaction(*id)
+            .and_then(|info| info.depth)
+            .map_or_else(|| "-".to_owned(), |d| d.to_string());
+
+        println!("{} - {}", ledger.display_id(*id), depth);
+    }
+
+    println!("--------------------------------");
+
+    Ok(())
+}
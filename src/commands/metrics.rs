@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::metrics;
+
+/// Prints the statistics in the Prometheus text format or writes them as a node_exporter textfile.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let output = metrics::render(&ledger.stats());
+
+    match matches.value_of("TEXTFILE") {
+        Some(path) => write_textfile(Path::new(path), &output)?,
+        None => print!("{output}"),
+    }
+
+    Ok(())
+}
+
+/// Writes the textfile atomically, so node_exporter never reads a partial file.
+fn write_textfile(path: &Path, output: &str) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    std::fs::write(&tmp_path, output)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger::Distribution;
+
+/// Prints the transactions confirmed by every milestone and the milestone statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let milestones = ledger.milestones();
+    let report = ledger.milestone_confirmations();
+
+    let intervals = Distribution::new(
+        milestones
+            .iter()
+            .filter_map(|m| m.interval.map(|i| i as f64)),
+    );
+    let confirmed = Distribution::new(milestones.iter().map(|m| m.confirmed.len() as f64));
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("---------- Milestones ----------");
+    println!("{}", milestones.len());
+
+    for milestone in &milestones {
+        let interval = milestone
+            .interval
+            .map_or_else(|| "-".to_owned(), |i| i.to_string());
+
+        println!(
+            "{} - {} {} {} {}",
+            milestone.index,
+            ledger.display_id(milestone.id),
+            milestone.timestamp,
+            interval,
+            milestone.confirmed.len()
+        );
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("AVG MILESTONE INTERVAL: {}", intervals.mean);
+    println!("MAX MILESTONE INTERVAL: {}", intervals.max);
+    println!("AVG CONFIRMED TXS PER MILESTONE: {}", confirmed.mean);
+    println!("MAX CONFIRMED TXS PER MILESTONE: {}", confirmed.max);
+    println!("---");
+    println!("CONFIRMED TXS: {}", report.confirmations.len());
+    println!("NEVER CONFIRMED TXS: {}", report.unconfirmed);
+    println!("AVG LATENCY: {}", report.latencies.mean);
+    println!("--------------------------------");
+
+    Ok(())
+}
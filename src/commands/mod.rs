@@ -0,0 +1,219 @@
+//! The `ledgerstats` application subcommands.
+
+pub mod audit;
+pub mod balances;
+pub mod confirmation;
+pub mod conflicts;
+pub mod convert;
+pub mod dominators;
+pub mod export;
+pub mod generate;
+pub mod heaviest_path;
+pub mod layers;
+pub mod lca;
+pub mod metrics;
+pub mod milestones;
+pub mod path;
+pub mod serve;
+pub mod simulate;
+pub mod stats;
+pub mod timestamps;
+pub mod watch;
+
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+
+use ledgerstats::ledger::{
+    self, Ledger, SimulationConfig, TimestampCheck, TimestampReport, TipSelection, Transactions,
+};
+
+/// A file path with a sample list of transactions.
+const DEFAULT_DB_FILE_PATH: &str = "./database.txt";
+
+/// Returns the database file path argument or the default one.
+fn db_file_path<'a>(matches: &'a ArgMatches) -> &'a str {
+    matches
+        .value_of("DB_FILE_PATH")
+        .unwrap_or(DEFAULT_DB_FILE_PATH)
+}
+
+/// Reads the transactions list from the database file in the input format.
+fn read_transactions(matches: &ArgMatches) -> Result<Transactions> {
+    match matches.value_of("INPUT_FORMAT") {
+        Some("binary") => return read_binary(db_file_path(matches)),
+        Some("text") if matches.is_present("PARALLEL") => {
+            return read_parallel(db_file_path(matches))
+        }
+        _ => {}
+    }
+
+    let database = std::fs::read_to_string(db_file_path(matches))?;
+
+    match matches.value_of("INPUT_FORMAT") {
+        Some("csv") => Ok(ledger::read_txs_from_csv(&database, delimiter(matches)?)?),
+        Some("jsonl") => read_jsonl(&database),
+        Some("hashed") => Ok(ledger::read_hashed_txs_from_db(&database)?.0),
+        _ => Ok(ledger::read_txs_from_db(&database)?),
+    }
+}
+
+/// Reads the transactions list from the memory-mapped binary database.
+#[allow(unsafe_code)]
+fn read_binary(db_file_path: &str) -> Result<Transactions> {
+    let file = std::fs::File::open(db_file_path)?;
+
+    // SAFETY: the mapping is only read while building the transactions list,
+    // the database must not be truncated by another process in the meantime.
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    Ok(ledger::read_txs_from_binary(&mmap)?)
+}
+
+/// Reads the transactions list from the memory-mapped text database in parallel.
+#[allow(unsafe_code)]
+fn read_parallel(db_file_path: &str) -> Result<Transactions> {
+    let file = std::fs::File::open(db_file_path)?;
+
+    // SAFETY: the mapping is only read while building the transactions list,
+    // the database must not be truncated by another process in the meantime.
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    Ok(ledger::par_read_txs_from_db(&mmap)?)
+}
+
+/// Reads the transactions list from the JSON Lines database.
+#[cfg(feature = "jsonl")]
+fn read_jsonl(database: &str) -> Result<Transactions> {
+    Ok(ledger::read_txs_from_jsonl(database)?)
+}
+
+/// Reads the transactions list from the JSON Lines database.
+#[cfg(not(feature = "jsonl"))]
+fn read_jsonl(_database: &str) -> Result<Transactions> {
+    bail!("the JSON Lines format requires the 'jsonl' feature")
+}
+
+/// Writes the transactions list in the output format.
+fn write_transactions(matches: &ArgMatches, transactions: &Transactions) -> Result<Vec<u8>> {
+    let database = match matches.value_of("OUTPUT_FORMAT") {
+        Some("binary") => return Ok(ledger::write_txs_to_binary(transactions)?),
+        Some("csv") => ledger::write_txs_to_csv(transactions, delimiter(matches)?)?,
+        Some("jsonl") => write_jsonl(transactions)?,
+        _ => ledger::write_txs_to_db(transactions)?,
+    };
+
+    Ok(database.into_bytes())
+}
+
+/// Writes the transactions list in the JSON Lines database format.
+#[cfg(feature = "jsonl")]
+fn write_jsonl(transactions: &Transactions) -> Result<String> {
+    Ok(ledger::write_txs_to_jsonl(transactions)?)
+}
+
+/// Writes the transactions list in the JSON Lines database format.
+#[cfg(not(feature = "jsonl"))]
+fn write_jsonl(_transactions: &Transactions) -> Result<String> {
+    bail!("the JSON Lines format requires the 'jsonl' feature")
+}
+
+/// Reads the CSV delimiter argument, `\t` stands for the tab character.
+fn delimiter(matches: &ArgMatches) -> Result<u8> {
+    match matches.value_of("DELIMITER").unwrap_or(",") {
+        "\\t" => Ok(b'\t'),
+        delimiter if delimiter.len() == 1 => Ok(delimiter.as_bytes()[0]),
+        delimiter => bail!("invalid CSV delimiter: '{delimiter}'"),
+    }
+}
+
+/// Reads the ledger from the database file and the optional milestones file.
+fn read_ledger(matches: &ArgMatches) -> Result<Ledger> {
+    let mut ledger = if matches.value_of("INPUT_FORMAT") == Some("hashed") {
+        let database = std::fs::read_to_string(db_file_path(matches))?;
+        let (transactions, hashes) = ledger::read_hashed_txs_from_db(&database)?;
+
+        let mut ledger = Ledger::new(transactions);
+        ledger.set_hashes(hashes);
+        ledger
+    } else {
+        Ledger::new(read_transactions(matches)?)
+    };
+
+    if let Some(milestones_file_path) = matches.value_of("MILESTONES") {
+        let milestones = std::fs::read_to_string(milestones_file_path)?;
+        let milestones = match ledger.hashes() {
+            Some(hashes) => hashes.read_milestones(&milestones)?,
+            None => ledger::read_milestones(&milestones)?,
+        };
+
+        ledger.set_milestones(milestones)?;
+    }
+
+    check_timestamps(&ledger, matches)?;
+
+    Ok(ledger)
+}
+
+/// Applies the timestamp check option to the ledger, the violations are reported to stderr or are an error.
+fn check_timestamps(ledger: &Ledger, matches: &ArgMatches) -> Result<()> {
+    if let Some(check) = timestamp_check(matches) {
+        warn_timestamps(&ledger.validate_timestamps(check)?);
+    }
+
+    Ok(())
+}
+
+/// Reads the timestamp check option, `None` if the check is off.
+fn timestamp_check(matches: &ArgMatches) -> Option<TimestampCheck> {
+    match matches.value_of("TIMESTAMP_CHECK") {
+        Some("error") => Some(TimestampCheck::Error),
+        Some("warn") => Some(TimestampCheck::Warn),
+        _ => None,
+    }
+}
+
+/// Reports the timestamp violations to stderr.
+fn warn_timestamps(report: &TimestampReport) {
+    if !report.violations.is_empty() {
+        eprintln!(
+            "WARNING: {} transactions are timestamped before their parents, max violation {}",
+            report.violations.len(),
+            report.magnitudes.max
+        );
+    }
+}
+
+/// Prints the database information block.
+fn print_database(db_file_path: &str) {
+    println!("----------- Database -----------");
+    println!("{db_file_path}");
+    println!("--------------------------------");
+    println!();
+}
+
+/// Reads the tangle growth parameters shared by the simulation subcommands.
+fn simulation_config(
+    matches: &ArgMatches,
+    tip_selection: TipSelection,
+) -> Result<SimulationConfig> {
+    let config = SimulationConfig {
+        delay: value_t!(matches, "DELAY", f64)?,
+        tip_selection,
+        parents: value_t!(matches, "PARENTS", usize)?,
+        walk_window: value_t!(matches, "WALK_WINDOW", f64)?,
+        seed: value_t!(matches, "SEED", u64)?,
+    };
+
+    if !(1..=ledger::MAX_PARENTS).contains(&config.parents) {
+        bail!(
+            "the parents number must be between 1 and {}",
+            ledger::MAX_PARENTS
+        );
+    }
+
+    if config.walk_window < config.delay {
+        bail!("the walk window can not be less than the delay");
+    }
+
+    Ok(config)
+}
@@ -0,0 +1,46 @@
+use anyhow::{anyhow, Result};
+use clap::ArgMatches;
+use itertools::Itertools;
+
+use ledgerstats::ledger::{Ledger, TxId};
+
+/// Prints the shortest and the longest paths between the transactions.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let from = resolve(&ledger, matches.value_of("FROM").unwrap_or_default())?;
+    let to = resolve(&ledger, matches.value_of("TO").unwrap_or_default())?;
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("------------ Paths -------------");
+    print_path(&ledger, "SHORTEST", ledger.shortest_path(from, to));
+    print_path(&ledger, "LONGEST", ledger.longest_path(from, to));
+    println!("--------------------------------");
+
+    Ok(())
+}
+
+/// Returns the id of the transaction argument.
+fn resolve(ledger: &Ledger, id: &str) -> Result<TxId> {
+    ledger
+        .resolve_id(id)
+        .ok_or_else(|| anyhow!("unknown transaction: '{id}'"))
+}
+
+/// Prints the path and its length, or `-` if there is no path.
+fn print_path(ledger: &Ledger, name: &str, path: Option<Vec<TxId>>) {
+    match path {
+        Some(path) => {
+            println!("{name} LENGTH: {}", path.len() - 1);
+            println!(
+                "{name} PATH: {}",
+                path.iter().map(|id| ledger.display_id(*id)).join(" ")
+            );
+        }
+        None => {
+            println!("{name} LENGTH: -");
+            println!("{name} PATH: -");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::server::Server;
+
+/// Loads the database and serves its statistics over HTTP.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+    let server = Server::bind(value_t!(matches, "BIND", String)?.as_str(), ledger)?;
+
+    super::print_database(super::db_file_path(matches));
+
+    if let Some(addr) = server.local_addr() {
+        println!("Listening on http://{addr}");
+    }
+
+    server.run()?;
+
+    Ok(())
+}
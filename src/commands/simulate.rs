@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger::{self, Ledger, TipSelection};
+
+/// Replays the database timestamps with every requested tip selection
+/// and prints the statistics of the regrown ledgers next to the original ones.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let db_file_path = super::db_file_path(matches);
+
+    let transactions = super::read_transactions(matches)?;
+
+    let mut strategies = Vec::new();
+
+    for strategy in matches.values_of("TIP_SELECTION").into_iter().flatten() {
+        match strategy {
+            "walk" => {
+                for alpha in values_t!(matches, "ALPHA", f64)? {
+                    strategies.push(TipSelection::RandomWalk { alpha });
+                }
+            }
+            _ => strategies.push(TipSelection::Uniform),
+        }
+    }
+
+    super::print_database(db_file_path);
+
+    println!("----------- Original -----------");
+    super::stats::print_stats(&Ledger::new(transactions.clone()));
+
+    for tip_selection in strategies {
+        let config = super::simulation_config(matches, tip_selection)?;
+
+        println!();
+        println!("--- {tip_selection} ---");
+        super::stats::print_stats(&Ledger::new(ledger::replay_txs(&transactions, &config)));
+    }
+
+    Ok(())
+}
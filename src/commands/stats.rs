@@ -0,0 +1,25 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+use ledgerstats::ledger::Ledger;
+
+/// Prints the statistics of the provided database.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("{ledger:?}");
+    println!();
+
+    println!("------------ Stats -------------");
+    print_stats(&ledger);
+
+    Ok(())
+}
+
+/// Prints the ledger statistics block.
+pub fn print_stats(ledger: &Ledger) {
+    println!("{}", ledger.stats());
+    println!("--------------------------------");
+}
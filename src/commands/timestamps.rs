@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Prints the transactions timestamped before their parents and the violation statistics.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ledger = super::read_ledger(matches)?;
+
+    let report = ledger.timestamp_violations();
+
+    super::print_database(super::db_file_path(matches));
+
+    println!("---------- Violations ----------");
+
+    for violation in &report.violations {
+        println!(
+            "{} - {} {}",
+            ledger.display_id(violation.id),
+            ledger.display_id(violation.parent),
+            violation.magnitude
+        );
+    }
+
+    println!();
+    println!("------------ Stats -------------");
+    println!("VIOLATING TXS: {}", report.violations.len());
+    println!("VIOLATING REFS: {}", report.violating_references);
+    println!("---");
+    println!("AVG VIOLATION: {}", report.magnitudes.mean);
+    println!("MEDIAN VIOLATION: {}", report.magnitudes.median);
+    println!("MAX VIOLATION: {}", report.magnitudes.max);
+    println!("--------------------------------");
+
+    Ok(())
+}
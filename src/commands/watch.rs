@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use clap::ArgMatches;
+
+use ledgerstats::ledger::{Ledger, Transactions, TxStream};
+
+/// Follows the growing database and reprints the statistics at the given interval.
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let db_file_path = super::db_file_path(matches);
+    let interval = value_t!(matches, "INTERVAL", f64)?;
+    let json = matches.is_present("JSON");
+
+    if !interval.is_finite() || interval <= 0.0 {
+        bail!("the interval must be a positive number of seconds");
+    }
+
+    let interval = Duration::try_from_secs_f64(interval)
+        .map_err(|e| anyhow!("the interval must be a positive number of seconds: {e}"))?;
+
+    if matches.value_of("INPUT_FORMAT") != Some("text") {
+        bail!("the watch mode supports only the text format");
+    }
+
+    let mut file = std::fs::File::open(db_file_path)?;
+    let mut stream = TxStream::new();
+    let mut ledger = Ledger::new(Transactions::new());
+
+    let mut chunk = Vec::new();
+    let mut position = file.read_to_end(&mut chunk)? as u64;
+
+    // The writer may still be appending the last line, so it stays pending until it is terminated
+    for tx in stream.push(&chunk)? {
+        ledger.add_transaction(tx)?;
+    }
+
+    super::check_timestamps(&ledger, matches)?;
+
+    if !json {
+        super::print_database(db_file_path);
+    }
+
+    loop {
+        if json {
+            println!("{}", serde_json::to_string(&ledger.stats())?);
+        } else {
+            println!("------------ Stats -------------");
+            super::stats::print_stats(&ledger);
+            println!();
+        }
+
+        std::thread::sleep(interval);
+
+        if file.metadata()?.len() < position {
+            bail!("the database was truncated");
+        }
+
+        chunk.clear();
+        position += file.read_to_end(&mut chunk)? as u64;
+
+        let appended = stream
+            .push(&chunk)?
+            .into_iter()
+            .map(|tx| ledger.add_transaction(tx))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+
+        // Only the references from or to the appended transactions are new
+        if let Some(check) = super::timestamp_check(matches).filter(|_| !appended.is_empty()) {
+            super::warn_timestamps(&ledger.validate_new_timestamps(&appended, check)?);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+
+/// The attack detection configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditConfig {
+    /// The timestamp gap to the youngest parent above which an approval is lazy.
+    pub lazy_gap: Timestamp,
+    /// The minimum number of transactions of a reported parasite chain.
+    pub min_chain_size: usize,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            lazy_gap: 10,
+            min_chain_size: 3,
+        }
+    }
+}
+
+/// A transaction approving only old transactions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LazyApproval {
+    /// The transaction id.
+    pub id: TxId,
+    /// The timestamp gap to the youngest parent.
+    pub gap: Timestamp,
+}
+
+/// A subgraph growing from a lazy approval and referencing only itself and old transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParasiteChain {
+    /// The lazy approval attaching the chain to the old part of the tangle.
+    pub root: TxId,
+    /// The timestamp gap of the root to its youngest parent.
+    pub gap: Timestamp,
+    /// The chain transactions, the root included.
+    pub transactions: BTreeSet<TxId>,
+    /// The chain size weighted by how old its attachment is, `size * gap / lazy_gap`.
+    pub score: f64,
+}
+
+/// The detected lazy approvals and parasite chains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditReport {
+    /// The lazy approvals ordered by id.
+    pub lazy_approvals: Vec<LazyApproval>,
+    /// The lazy approval gaps distribution.
+    pub lazy_gaps: Distribution,
+    /// The parasite chains ordered by descending score.
+    pub parasite_chains: Vec<ParasiteChain>,
+    /// The sum of the parasite chain scores.
+    pub score: f64,
+}
+
+/// Detects the lazy approvals and the parasite chains growing from them.
+///
+/// An approval is lazy if the transaction is more than `lazy_gap` younger than its youngest parent.
+/// A parasite chain starts at a lazy approval and includes every approver whose parents are either
+/// in the chain or lazily approved, transaction 1 is considered old.
+pub fn audit(transactions: &Transactions, graph: &Graph, config: &AuditConfig) -> AuditReport {
+    let gap = |id: TxId, parent: TxId| {
+        transactions
+            .get(&parent)
+            .map(|p| transactions[&id].timestamp.saturating_sub(p.timestamp))
+    };
+
+    let mut lazy_approvals = graph
+        .topological_order()
+        .iter()
+        .filter(|id| transactions.contains_key(id))
+        .filter_map(|id| {
+            graph
+                .parents(*id)
+                .filter_map(|parent| gap(*id, parent))
+                .min()
+                .filter(|gap| *gap > config.lazy_gap)
+                .map(|gap| LazyApproval { id: *id, gap })
+        })
+        .collect::<Vec<_>>();
+
+    let is_old = |id: TxId, parent: TxId| gap(id, parent).is_none_or(|g| g > config.lazy_gap);
+
+    let mut chained = HashSet::new();
+    let mut parasite_chains = Vec::new();
+
+    for lazy in &lazy_approvals {
+        if chained.contains(&lazy.id) {
+            continue;
+        }
+
+        let mut chain = BTreeSet::from([lazy.id]);
+        let mut stack = vec![lazy.id];
+
+        while let Some(id) = stack.pop() {
+            for approver in graph.approvers(id).into_iter().flatten().copied() {
+                if chain.contains(&approver) {
+                    continue;
+                }
+
+                if graph
+                    .parents(approver)
+                    .all(|parent| chain.contains(&parent) || is_old(approver, parent))
+                {
+                    chain.insert(approver);
+                    stack.push(approver);
+                }
+            }
+        }
+
+        if chain.len() >= config.min_chain_size {
+            chained.extend(chain.iter().copied());
+            parasite_chains.push(ParasiteChain {
+                root: lazy.id,
+                gap: lazy.gap,
+                score: chain.len() as f64 * lazy.gap as f64 / config.lazy_gap.max(1) as f64,
+                transactions: chain,
+            });
+        }
+    }
+
+    parasite_chains.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.root.cmp(&b.root)));
+
+    lazy_approvals.sort_by_key(|lazy| lazy.id);
+
+    AuditReport {
+        lazy_gaps: Distribution::new(lazy_approvals.iter().map(|lazy| lazy.gap as f64)),
+        score: parasite_chains
+            .iter()
+            .fold(0.0, |sum, chain| sum + chain.score),
+        lazy_approvals,
+        parasite_chains,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn detect_parasite_chain() {
+        let mut transactions = Transactions::new();
+
+        // The honest tangle.
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(2, 2, 10));
+        transactions.insert(4, Transaction::new(3, 3, 20));
+        transactions.insert(5, Transaction::new(4, 4, 30));
+        // The parasite chain attached to transaction 2.
+        transactions.insert(6, Transaction::new(2, 2, 25));
+        transactions.insert(7, Transaction::new(6, 2, 26));
+        transactions.insert(8, Transaction::new(7, 3, 27));
+        // An honest transaction approving the chain.
+        transactions.insert(9, Transaction::new(8, 5, 31));
+
+        let graph = Graph::new(&transactions);
+        let report = audit(&transactions, &graph, &AuditConfig::default());
+
+        assert_eq!(report.lazy_approvals, vec![LazyApproval { id: 6, gap: 25 }]);
+        assert_eq!(
+            report.parasite_chains,
+            vec![ParasiteChain {
+                root: 6,
+                gap: 25,
+                transactions: BTreeSet::from([6, 7, 8]),
+                score: 7.5,
+            }]
+        );
+        assert_eq!(report.score, 7.5);
+    }
+
+    #[test]
+    fn lazy_approval_without_chain() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(2, 2, 5));
+        transactions.insert(4, Transaction::new(2, 3, 50));
+
+        let graph = Graph::new(&transactions);
+        let report = audit(&transactions, &graph, &AuditConfig::default());
+
+        assert_eq!(report.lazy_approvals, vec![LazyApproval { id: 4, gap: 45 }]);
+        assert!(report.parasite_chains.is_empty());
+        assert_eq!(report.score, 0.0);
+    }
+}
@@ -0,0 +1,327 @@
+use super::error::LedgerError;
+use super::graph::Transactions;
+use super::transaction::{Timestamp, Transaction, TxId, TxRecord, MAX_PARENTS};
+use super::Result;
+
+/// The magic bytes starting every binary database.
+pub const BINARY_MAGIC: [u8; 4] = *b"LDGR";
+/// The current binary format version.
+pub const BINARY_VERSION: u16 = 2;
+
+/// The header size: magic, version, record size and records number.
+const HEADER_SIZE: usize = 16;
+/// The version 1 record size: id, left parent, right parent and timestamp as little-endian `u64`s.
+const RECORD_SIZE_V1: usize = 32;
+/// The record size: id, timestamp and `MAX_PARENTS` zero-padded parents as little-endian `u64`s.
+const RECORD_SIZE: usize = (2 + MAX_PARENTS) * 8;
+/// The trailing CRC-32 checksum size.
+const CHECKSUM_SIZE: usize = 4;
+
+/// A binary database borrowed from a byte slice, e.g. a memory-mapped file.
+///
+/// The layout is a 16-byte header(magic `LDGR`, `u16` version, `u16` record size, `u64` records number),
+/// fixed-width records of little-endian `u64`s(id, timestamp and `MAX_PARENTS` parents padded with zeros)
+/// and the CRC-32 checksum of all the preceding bytes. Records are decoded lazily.
+/// Version 1 records of two parents(id, left, right, timestamp) are still readable.
+#[derive(Debug, Clone, Copy)]
+pub struct BinaryLedger<'a> {
+    /// The records bytes.
+    records: &'a [u8],
+    /// The format version of the records.
+    version: u16,
+}
+
+impl<'a> BinaryLedger<'a> {
+    /// Creates a new `BinaryLedger` instance validating the header, the size and the checksum of the bytes.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        if bytes.len() < HEADER_SIZE + CHECKSUM_SIZE || bytes[0..4] != BINARY_MAGIC {
+            return Err(LedgerError::BinaryFormatError("invalid magic".to_owned()));
+        }
+
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+
+        let expected_record_size = match version {
+            1 => RECORD_SIZE_V1,
+            BINARY_VERSION => RECORD_SIZE,
+            _ => {
+                return Err(LedgerError::BinaryFormatError(format!(
+                    "unsupported version '{version}'"
+                )))
+            }
+        };
+
+        let record_size = u16::from_le_bytes([bytes[6], bytes[7]]) as usize;
+
+        if record_size != expected_record_size {
+            return Err(LedgerError::BinaryFormatError(format!(
+                "unsupported record size '{record_size}'"
+            )));
+        }
+
+        let count = read_u64(bytes, 8) as usize;
+        let records_size = bytes.len() - HEADER_SIZE - CHECKSUM_SIZE;
+
+        if count.checked_mul(record_size) != Some(records_size) {
+            return Err(LedgerError::WrongTxNumberError(
+                count,
+                records_size / record_size,
+            ));
+        }
+
+        let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+        let expected = u32::from_le_bytes(checksum.try_into().expect("the checksum has 4 bytes"));
+        let actual = crc32fast::hash(content);
+
+        if expected != actual {
+            return Err(LedgerError::ChecksumError(expected, actual));
+        }
+
+        Ok(Self {
+            records: &content[HEADER_SIZE..],
+            version,
+        })
+    }
+
+    /// Returns the format version of the records.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.records.len() / self.record_size()
+    }
+
+    /// Returns true if there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the id and the transaction of the record at the provided index.
+    pub fn get(&self, index: usize) -> Option<(TxId, Transaction)> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let offset = index * self.record_size();
+        let field = |i: usize| read_u64(self.records, offset + i * 8);
+
+        let tx = match self.version {
+            1 => Transaction::new(field(1) as TxId, field(2) as TxId, field(3) as Timestamp),
+            _ => Transaction::with_parents(
+                (2..2 + MAX_PARENTS)
+                    .map(field)
+                    .take_while(|parent| *parent != 0)
+                    .map(|parent| parent as TxId)
+                    .collect(),
+                field(1) as Timestamp,
+            ),
+        };
+
+        Some((field(0) as TxId, tx))
+    }
+
+    /// Returns the size of the records of the format version.
+    fn record_size(&self) -> usize {
+        match self.version {
+            1 => RECORD_SIZE_V1,
+            _ => RECORD_SIZE,
+        }
+    }
+
+    /// Returns an iterator over the records.
+    pub fn iter(&self) -> impl Iterator<Item = (TxId, Transaction)> + 'a {
+        let ledger = *self;
+
+        (0..ledger.len()).filter_map(move |index| ledger.get(index))
+    }
+
+    /// Returns the transactions list, validating the ids.
+    pub fn to_transactions(&self) -> Result<Transactions> {
+        super::txs_from_records(self.iter().map(|(id, tx)| Ok(TxRecord::new(id, &tx))))
+    }
+}
+
+/// Reads the provided binary database and returns a transactions list.
+pub fn read_txs_from_binary(bytes: &[u8]) -> Result<Transactions> {
+    BinaryLedger::parse(bytes)?.to_transactions()
+}
+
+/// Writes the provided transactions list in the binary database format, ordered by id.
+/// The value transfers, more than `MAX_PARENTS` parents and the zero parent padding id can't be represented.
+pub fn write_txs_to_binary(transactions: &Transactions) -> Result<Vec<u8>> {
+    if let Some((id, _)) = transactions.iter().find(|(_, tx)| tx.has_transfers()) {
+        return Err(LedgerError::BinaryFormatError(format!(
+            "transaction {id} has value transfers"
+        )));
+    }
+
+    if let Some((id, tx)) = transactions
+        .iter()
+        .find(|(_, tx)| tx.parents.len() > MAX_PARENTS)
+    {
+        return Err(LedgerError::BinaryFormatError(format!(
+            "transaction {id} has {} parents",
+            tx.parents.len()
+        )));
+    }
+
+    if let Some((id, _)) = transactions.iter().find(|(_, tx)| tx.parents.contains(&0)) {
+        return Err(LedgerError::BinaryFormatError(format!(
+            "transaction {id} has the parent 0"
+        )));
+    }
+
+    let mut bytes =
+        Vec::with_capacity(HEADER_SIZE + transactions.len() * RECORD_SIZE + CHECKSUM_SIZE);
+
+    bytes.extend_from_slice(&BINARY_MAGIC);
+    bytes.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(RECORD_SIZE as u16).to_le_bytes());
+    bytes.extend_from_slice(&(transactions.len() as u64).to_le_bytes());
+
+    for record in super::records_from_txs(transactions) {
+        let mut parents = [0; MAX_PARENTS];
+
+        for (padded, parent) in parents.iter_mut().zip(&record.parents) {
+            *padded = *parent as u64;
+        }
+
+        bytes.extend_from_slice(&(record.id as u64).to_le_bytes());
+        bytes.extend_from_slice(&record.timestamp.to_le_bytes());
+        parents
+            .iter()
+            .for_each(|parent| bytes.extend_from_slice(&parent.to_le_bytes()));
+    }
+
+    let checksum = crc32fast::hash(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+
+    Ok(bytes)
+}
+
+/// Reads the little-endian `u64` at the provided offset.
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(
+        bytes[offset..offset + 8]
+            .try_into()
+            .expect("the slice has 8 bytes"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_transactions() -> Transactions {
+        Transactions::from([
+            (2, Transaction::new(1, 1, 0)),
+            (3, Transaction::new(1, 2, 0)),
+            (4, Transaction::new(2, 2, 1)),
+        ])
+    }
+
+    #[test]
+    fn binary_roundtrip() {
+        let bytes = write_txs_to_binary(&sample_transactions()).unwrap();
+
+        assert_eq!(bytes.len(), HEADER_SIZE + 3 * RECORD_SIZE + CHECKSUM_SIZE);
+        assert_eq!(bytes[0..8], [b'L', b'D', b'G', b'R', 2, 0, 80, 0]);
+
+        let ledger = BinaryLedger::parse(&bytes).unwrap();
+
+        assert_eq!(ledger.len(), 3);
+        assert_eq!(ledger.get(1), Some((3, Transaction::new(1, 2, 0))));
+        assert_eq!(ledger.get(3), None);
+        assert_eq!(ledger.to_transactions().unwrap(), sample_transactions());
+    }
+
+    #[test]
+    fn invalid_binary() {
+        let bytes = write_txs_to_binary(&sample_transactions()).unwrap();
+
+        assert!(matches!(
+            read_txs_from_binary(&bytes[1..]),
+            Err(LedgerError::BinaryFormatError(_))
+        ));
+        assert!(matches!(
+            read_txs_from_binary(&bytes[..bytes.len() - 1]),
+            Err(LedgerError::WrongTxNumberError(3, 2))
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupted[HEADER_SIZE] ^= 1;
+
+        assert!(matches!(
+            read_txs_from_binary(&corrupted),
+            Err(LedgerError::ChecksumError(_, _))
+        ));
+
+        let mut version = bytes;
+        version[4] = 3;
+
+        assert_eq!(
+            read_txs_from_binary(&version),
+            Err(LedgerError::BinaryFormatError(
+                "unsupported version '3'".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn read_version_1() {
+        let mut bytes = b"LDGR\x01\x00\x20\x00".to_vec();
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+
+        for field in [2u64, 1, 1, 7] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+
+        let checksum = crc32fast::hash(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        let ledger = BinaryLedger::parse(&bytes).unwrap();
+
+        assert_eq!(ledger.version(), 1);
+        assert_eq!(ledger.get(0), Some((2, Transaction::new(1, 1, 7))));
+    }
+
+    #[test]
+    fn binary_roundtrip_of_variable_parents() {
+        let transactions = Transactions::from([
+            (2, Transaction::with_parents(vec![1], 0)),
+            (
+                3,
+                Transaction::with_parents(vec![1, 2, 2, 1, 2, 1, 2, 1], 1),
+            ),
+        ]);
+
+        let bytes = write_txs_to_binary(&transactions).unwrap();
+
+        assert_eq!(read_txs_from_binary(&bytes).unwrap(), transactions);
+    }
+
+    #[test]
+    fn write_too_many_parents() {
+        let transactions = Transactions::from([(2, Transaction::with_parents(vec![1; 9], 0))]);
+
+        assert_eq!(
+            write_txs_to_binary(&transactions),
+            Err(LedgerError::BinaryFormatError(
+                "transaction 2 has 9 parents".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn write_zero_parent() {
+        let transactions = Transactions::from([(2, Transaction::new(1, 0, 0))]);
+
+        assert_eq!(
+            write_txs_to_binary(&transactions),
+            Err(LedgerError::BinaryFormatError(
+                "transaction 2 has the parent 0".to_owned()
+            ))
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeSet;
+
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+use super::{LedgerError, Result};
+
+/// Defines how the timestamp violations are treated.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TimestampCheck {
+    /// The violations are reported.
+    Warn,
+    /// Any violation is an error.
+    Error,
+}
+
+/// A transaction timestamped before one of its parents.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TimestampViolation {
+    /// The transaction id.
+    pub id: TxId,
+    /// The youngest parent timestamped after the transaction.
+    pub parent: TxId,
+    /// How much earlier than the parent the transaction is timestamped.
+    pub magnitude: Timestamp,
+}
+
+/// The timestamp violations of the ledger.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampReport {
+    /// The violating transactions ordered by id, with their largest violation.
+    pub violations: Vec<TimestampViolation>,
+    /// The number of parent references violating the timestamp order.
+    pub violating_references: usize,
+    /// The largest violation magnitudes distribution.
+    pub magnitudes: Distribution,
+}
+
+impl TimestampReport {
+    /// Returns an error for the largest violation if the check is strict.
+    pub fn check(self, check: TimestampCheck) -> Result<Self> {
+        let largest = self
+            .violations
+            .iter()
+            .max_by_key(|v| (v.magnitude, std::cmp::Reverse(v.id)));
+
+        match (check, largest) {
+            (TimestampCheck::Error, Some(v)) => Err(LedgerError::TimestampViolationError(
+                v.id,
+                v.parent,
+                v.magnitude,
+            )),
+            _ => Ok(self),
+        }
+    }
+}
+
+/// Returns the transactions timestamped before their parents, transaction 1 has no timestamp.
+pub fn check_timestamps(transactions: &Transactions, graph: &Graph) -> TimestampReport {
+    check_references(transactions, graph, transactions.keys().copied(), |_, _| {
+        true
+    })
+}
+
+/// Returns the violations of the references from or to the provided transactions only,
+/// the references between the other transactions are not checked again.
+pub fn check_new_timestamps(
+    transactions: &Transactions,
+    graph: &Graph,
+    ids: &BTreeSet<TxId>,
+) -> TimestampReport {
+    // Earlier transactions may reference the new ones as parents
+    let children = ids
+        .iter()
+        .flat_map(|id| graph.approvers(*id).into_iter().flatten())
+        .chain(ids)
+        .copied()
+        .collect::<BTreeSet<_>>();
+
+    check_references(transactions, graph, children, |id, parent| {
+        ids.contains(&id) || ids.contains(&parent)
+    })
+}
+
+/// Returns the violations of the parent references of the transactions accepted by the filter.
+fn check_references(
+    transactions: &Transactions,
+    graph: &Graph,
+    ids: impl IntoIterator<Item = TxId>,
+    filter: impl Fn(TxId, TxId) -> bool,
+) -> TimestampReport {
+    let mut violations = Vec::new();
+    let mut violating_references = 0;
+
+    for id in ids {
+        let Some(tx) = transactions.get(&id) else {
+            continue;
+        };
+
+        let parents = graph
+            .parents(id)
+            .filter(|parent| filter(id, *parent))
+            .filter_map(|parent| transactions.get(&parent).map(|p| (parent, p.timestamp)))
+            .filter(|(_, timestamp)| *timestamp > tx.timestamp)
+            .collect::<Vec<_>>();
+
+        violating_references += parents.len();
+
+        if let Some((parent, timestamp)) = parents.into_iter().max_by_key(|(p, ts)| (*ts, *p)) {
+            violations.push(TimestampViolation {
+                id,
+                parent,
+                magnitude: timestamp - tx.timestamp,
+            });
+        }
+    }
+
+    violations.sort_by_key(|v| v.id);
+
+    TimestampReport {
+        magnitudes: Distribution::new(violations.iter().map(|v| v.magnitude as f64)),
+        violations,
+        violating_references,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn timestamp_violations() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 5));
+        transactions.insert(3, Transaction::new(2, 2, 3));
+        transactions.insert(4, Transaction::new(2, 3, 4));
+        transactions.insert(5, Transaction::new(3, 4, 6));
+
+        let graph = Graph::new(&transactions);
+        let report = check_timestamps(&transactions, &graph);
+
+        assert_eq!(
+            report.violations,
+            vec![
+                TimestampViolation {
+                    id: 3,
+                    parent: 2,
+                    magnitude: 2
+                },
+                TimestampViolation {
+                    id: 4,
+                    parent: 2,
+                    magnitude: 1
+                },
+            ]
+        );
+        assert_eq!(report.violating_references, 2);
+        assert_eq!(report.magnitudes.max, 2.0);
+
+        assert_eq!(
+            report.clone().check(TimestampCheck::Warn),
+            Ok(report.clone())
+        );
+        assert_eq!(
+            report.check(TimestampCheck::Error),
+            Err(LedgerError::TimestampViolationError(3, 2, 2))
+        );
+    }
+
+    #[test]
+    fn new_timestamp_violations() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 5));
+        transactions.insert(3, Transaction::new(2, 2, 3));
+        transactions.insert(4, Transaction::new(2, 5, 4));
+        transactions.insert(5, Transaction::new(3, 3, 6));
+
+        let graph = Graph::new(&transactions);
+        let report = check_new_timestamps(&transactions, &graph, &BTreeSet::from([5]));
+
+        // Transaction 3 violates the order with transaction 2 too, but the reference is not new
+        assert_eq!(
+            report.violations,
+            vec![TimestampViolation {
+                id: 4,
+                parent: 5,
+                magnitude: 2
+            }]
+        );
+        assert_eq!(report.violating_references, 1);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
+
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+
+/// Type alias for confirmation timestamps list.
+pub type Confirmations = HashMap<TxId, Timestamp>;
+
+/// A rule that defines when a transaction becomes confirmed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConfirmationRule {
+    /// The transaction's cumulative weight reaches the threshold.
+    CumulativeWeight(u64),
+    /// The transaction is in the past cone of at least the given number of tips.
+    ApprovingTips(usize),
+}
+
+impl std::fmt::Display for ConfirmationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfirmationRule::CumulativeWeight(threshold) => {
+                write!(f, "cumulative weight >= {threshold}")
+            }
+            ConfirmationRule::ApprovingTips(tips) => write!(f, "approved by >= {tips} tips"),
+        }
+    }
+}
+
+/// The confirmation simulation results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmationReport {
+    /// The confirmation timestamps of the confirmed transactions.
+    pub confirmations: Confirmations,
+    /// The confirmation latencies distribution.
+    pub latencies: Distribution,
+    /// The number of transactions which were never confirmed.
+    pub unconfirmed: usize,
+}
+
+impl ConfirmationReport {
+    /// Creates a new `ConfirmationReport` instance from the confirmation timestamps.
+    /// Transaction 1 is not included.
+    pub fn new(transactions: &Transactions, mut confirmations: Confirmations) -> Self {
+        confirmations.retain(|id, _| transactions.contains_key(id));
+
+        let latencies = Distribution::new(
+            confirmations
+                .iter()
+                .map(|(id, ts)| ts.saturating_sub(transactions[id].timestamp) as f64),
+        );
+
+        Self {
+            unconfirmed: transactions.len() - confirmations.len(),
+            confirmations,
+            latencies,
+        }
+    }
+}
+
+/// Replays the transactions in timestamp order and records when every transaction becomes confirmed.
+pub fn replay_confirmations(
+    transactions: &Transactions,
+    graph: &Graph,
+    rule: ConfirmationRule,
+) -> Confirmations {
+    let mut replay = Replay {
+        graph,
+        arrived: HashSet::from([1]),
+        tips: BTreeSet::from([1]),
+        weights: HashMap::new(),
+        confirmations: Confirmations::new(),
+    };
+
+    let arrivals = transactions
+        .iter()
+        .sorted_by_key(|(id, tx)| (tx.timestamp, **id))
+        .group_by(|(_, tx)| tx.timestamp);
+
+    for (timestamp, group) in &arrivals {
+        for (id, _) in group {
+            replay.arrive(*id);
+
+            if let ConfirmationRule::CumulativeWeight(threshold) = rule {
+                replay.add_weight(*id, threshold, timestamp);
+            }
+        }
+
+        if let ConfirmationRule::ApprovingTips(tips) = rule {
+            replay.count_tips(tips, timestamp);
+        }
+    }
+
+    replay.confirmations
+}
+
+/// The confirmation replay state.
+struct Replay<'a> {
+    /// The transactions graph.
+    graph: &'a Graph,
+    /// The already arrived transactions.
+    arrived: HashSet<TxId>,
+    /// The arrived transactions without arrived approvers.
+    tips: BTreeSet<TxId>,
+    /// The cumulative weights of the not yet confirmed transactions.
+    weights: HashMap<TxId, u64>,
+    /// The confirmation timestamps.
+    confirmations: Confirmations,
+}
+
+impl Replay<'_> {
+    /// Marks the transaction as arrived and updates the tips.
+    fn arrive(&mut self, id: TxId) {
+        self.arrived.insert(id);
+
+        let approved = self
+            .graph
+            .approvers(id)
+            .is_some_and(|approvers| approvers.iter().any(|a| self.arrived.contains(a)));
+
+        if !approved {
+            self.tips.insert(id);
+        }
+
+        for parent in self.graph.parents(id) {
+            self.tips.remove(&parent);
+        }
+    }
+
+    /// Adds the new transaction's weight to its not yet confirmed past cone.
+    /// Ancestors of a confirmed transaction are always heavier, so they are confirmed too.
+    fn add_weight(&mut self, id: TxId, threshold: u64, timestamp: Timestamp) {
+        let mut visited = HashSet::from([id]);
+        let mut stack = vec![id];
+
+        while let Some(i) = stack.pop() {
+            let weight = self.weights.entry(i).or_default();
+            *weight += 1;
+
+            if *weight >= threshold {
+                self.weights.remove(&i);
+                self.confirmations.insert(i, timestamp);
+            }
+
+            for parent in self.graph.parents(i) {
+                if !self.confirmations.contains_key(&parent) && visited.insert(parent) {
+                    stack.push(parent);
+                }
+            }
+        }
+    }
+
+    /// Counts the tips approving every not yet confirmed transaction.
+    /// Ancestors of a confirmed transaction are approved by the same tips, so they are confirmed too.
+    fn count_tips(&mut self, threshold: usize, timestamp: Timestamp) {
+        let mut counter = HashMap::<TxId, usize>::new();
+
+        for tip in &self.tips {
+            let mut visited = HashSet::new();
+            let mut stack = vec![*tip];
+
+            while let Some(i) = stack.pop() {
+                for parent in self.graph.parents(i) {
+                    if !self.confirmations.contains_key(&parent) && visited.insert(parent) {
+                        *counter.entry(parent).or_default() += 1;
+                        stack.push(parent);
+                    }
+                }
+            }
+        }
+
+        for (id, count) in counter {
+            if count >= threshold {
+                self.confirmations.insert(id, timestamp);
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::confirmation::Confirmations;
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+use super::value::{self, Address};
+
+/// A rule that defines which transaction of a conflict set wins.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConflictResolution {
+    /// The first transaction whose cumulative weight reaches the threshold wins.
+    CumulativeWeight(u64),
+    /// The first transaction included by a milestone wins.
+    Milestones,
+}
+
+impl std::fmt::Display for ConflictResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConflictResolution::CumulativeWeight(threshold) => {
+                write!(f, "cumulative weight >= {threshold}")
+            }
+            ConflictResolution::Milestones => write!(f, "milestone inclusion"),
+        }
+    }
+}
+
+/// A set of concurrent transactions double-spending from the same address.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Conflict {
+    /// The double-spent address.
+    pub address: Address,
+    /// The conflicting transactions, every one of them conflicts with another one.
+    pub transactions: BTreeSet<TxId>,
+    /// The timestamp of the second conflicting transaction, when the conflict appears.
+    pub detected: Timestamp,
+    /// The transaction which won the conflict.
+    pub winner: Option<TxId>,
+    /// The time passed from the detection until the winner was confirmed.
+    pub resolution_time: Option<Timestamp>,
+    /// The transactions conflicting with the winner and their future cones.
+    pub orphaned: BTreeSet<TxId>,
+}
+
+/// The detected conflicts and their statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictReport {
+    /// The conflicts ordered by address and the smallest transaction id.
+    pub conflicts: Vec<Conflict>,
+    /// The number of conflicts with a winner.
+    pub resolved: usize,
+    /// The resolution times distribution of the resolved conflicts.
+    pub resolution_times: Distribution,
+    /// The orphaned cone sizes distribution of the resolved conflicts.
+    pub orphaned_cones: Distribution,
+    /// All the transactions orphaned by the resolved conflicts.
+    pub orphaned: BTreeSet<TxId>,
+}
+
+/// Detects the conflict sets and resolves them with the provided confirmation timestamps.
+///
+/// Only the spends funded by their own past cones are considered, the others are invalid and not conflicts.
+/// Two of them spending from the same address conflict if neither of them is in the past cone
+/// of the other one and together they spend more than the address balance in their shared past;
+/// a conflict set is a connected group of such transactions. The earliest confirmed transaction
+/// of a set wins, the transactions conflicting with it are orphaned together with their future cones.
+pub fn detect_conflicts(
+    transactions: &Transactions,
+    graph: &Graph,
+    confirmations: &Confirmations,
+) -> ConflictReport {
+    let funds = value::check_past_funds(transactions, graph);
+    let mut conflicts = Vec::new();
+
+    for (address, spends) in funds.spends.iter().filter(|(_, s)| s.len() > 1) {
+        let changes = &funds.changes[address];
+        let spends = spends
+            .iter()
+            .map(|spend| (spend.id, spend))
+            .collect::<HashMap<_, _>>();
+
+        let conflicting = |a: TxId, b: TxId| {
+            let (a, b) = (spends[&a], spends[&b]);
+
+            if a.id == b.id || a.approves(b) || b.approves(a) {
+                return false;
+            }
+
+            i128::from(a.amount) + i128::from(b.amount) > a.shared_balance(b, changes)
+        };
+
+        let spenders = spends.keys().copied().collect();
+
+        for set in conflict_sets(&spenders, &conflicting) {
+            conflicts.push(resolve(
+                address,
+                set,
+                &conflicting,
+                transactions,
+                graph,
+                confirmations,
+            ));
+        }
+    }
+
+    let resolved = conflicts
+        .iter()
+        .filter(|c| c.winner.is_some())
+        .collect::<Vec<_>>();
+
+    ConflictReport {
+        resolved: resolved.len(),
+        resolution_times: Distribution::new(
+            resolved
+                .iter()
+                .filter_map(|c| c.resolution_time.map(|t| t as f64)),
+        ),
+        orphaned_cones: Distribution::new(resolved.iter().map(|c| c.orphaned.len() as f64)),
+        orphaned: resolved
+            .iter()
+            .flat_map(|c| c.orphaned.iter().copied())
+            .collect(),
+        conflicts,
+    }
+}
+
+/// Returns the connected groups of at least two conflicting spenders.
+fn conflict_sets(
+    spenders: &BTreeSet<TxId>,
+    conflicting: &impl Fn(TxId, TxId) -> bool,
+) -> Vec<BTreeSet<TxId>> {
+    let mut unvisited = spenders.clone();
+    let mut sets = Vec::new();
+
+    while let Some(first) = unvisited.pop_first() {
+        let mut set = BTreeSet::from([first]);
+        let mut queue = vec![first];
+
+        while let Some(id) = queue.pop() {
+            let neighbours = unvisited
+                .iter()
+                .copied()
+                .filter(|other| conflicting(id, *other))
+                .collect::<Vec<_>>();
+
+            for neighbour in neighbours {
+                unvisited.remove(&neighbour);
+                set.insert(neighbour);
+                queue.push(neighbour);
+            }
+        }
+
+        if set.len() > 1 {
+            sets.push(set);
+        }
+    }
+
+    sets
+}
+
+/// Picks the earliest confirmed transaction of the set and collects the cones of the transactions
+/// conflicting with it.
+fn resolve(
+    address: &Address,
+    set: BTreeSet<TxId>,
+    conflicting: &impl Fn(TxId, TxId) -> bool,
+    transactions: &Transactions,
+    graph: &Graph,
+    confirmations: &Confirmations,
+) -> Conflict {
+    let mut timestamps = set
+        .iter()
+        .map(|id| transactions[id].timestamp)
+        .collect::<Vec<_>>();
+    timestamps.sort_unstable();
+    let detected = timestamps[1];
+
+    let winner = set
+        .iter()
+        .filter_map(|id| confirmations.get(id).map(|ts| (*ts, *id)))
+        .min();
+
+    let orphaned = match winner {
+        Some((_, winner)) => set
+            .iter()
+            .filter(|id| conflicting(winner, **id))
+            .flat_map(|id| graph.future_cone(*id).into_iter().chain([*id]))
+            .collect(),
+        None => BTreeSet::new(),
+    };
+
+    Conflict {
+        address: address.clone(),
+        transactions: set,
+        detected,
+        winner: winner.map(|(_, id)| id),
+        resolution_time: winner.map(|(ts, _)| ts.saturating_sub(detected)),
+        orphaned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::super::value::Transfer;
+    use super::*;
+
+    fn spend(tx: Transaction, address: &str) -> Transaction {
+        tx.with_transfers(vec![Transfer::new(address, 1)], vec![Transfer::new("Z", 1)])
+    }
+
+    fn issue(tx: Transaction, addresses: &[&str]) -> Transaction {
+        let outputs = addresses.iter().map(|a| Transfer::new(a, 1)).collect();
+
+        tx.with_transfers(vec![], outputs)
+    }
+
+    #[test]
+    fn detect_and_resolve_conflicts() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, issue(Transaction::new(1, 1, 0), &["A", "B"]));
+        transactions.insert(3, issue(Transaction::new(1, 1, 0), &["A"]));
+        transactions.insert(4, spend(Transaction::new(2, 2, 1), "A"));
+        transactions.insert(5, spend(Transaction::new(2, 2, 2), "A"));
+        transactions.insert(6, spend(Transaction::new(4, 3, 3), "A"));
+        transactions.insert(7, Transaction::new(5, 5, 4));
+        transactions.insert(8, spend(Transaction::new(2, 2, 5), "B"));
+
+        let graph = Graph::new(&transactions);
+        let confirmations = Confirmations::from([(4, 8), (5, 9)]);
+
+        let report = detect_conflicts(&transactions, &graph, &confirmations);
+
+        assert_eq!(
+            report.conflicts,
+            vec![Conflict {
+                address: "A".to_owned(),
+                transactions: BTreeSet::from([4, 5, 6]),
+                detected: 2,
+                winner: Some(4),
+                resolution_time: Some(6),
+                orphaned: BTreeSet::from([5, 7]),
+            }]
+        );
+        assert_eq!(report.resolved, 1);
+        assert_eq!(report.resolution_times.mean, 6.0);
+        assert_eq!(report.orphaned_cones.max, 2.0);
+        assert_eq!(report.orphaned, BTreeSet::from([5, 7]));
+    }
+
+    #[test]
+    fn unresolved_conflict() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, issue(Transaction::new(1, 1, 0), &["A"]));
+        transactions.insert(3, spend(Transaction::new(2, 2, 1), "A"));
+        transactions.insert(4, spend(Transaction::new(2, 2, 1), "A"));
+
+        let graph = Graph::new(&transactions);
+        let report = detect_conflicts(&transactions, &graph, &Confirmations::new());
+
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].winner, None);
+        assert_eq!(report.conflicts[0].resolution_time, None);
+        assert_eq!(report.resolved, 0);
+        assert!(report.orphaned.is_empty());
+    }
+
+    #[test]
+    fn concurrent_spends_within_balance() {
+        let mut transactions = Transactions::new();
+
+        // Issues 5 to A, then spends 2 and 3 of it concurrently
+        transactions.insert(
+            2,
+            Transaction::new(1, 1, 0).with_transfers(vec![], vec![Transfer::new("A", 5)]),
+        );
+        transactions.insert(
+            3,
+            Transaction::new(2, 2, 1)
+                .with_transfers(vec![Transfer::new("A", 2)], vec![Transfer::new("B", 2)]),
+        );
+        transactions.insert(
+            4,
+            Transaction::new(2, 2, 1)
+                .with_transfers(vec![Transfer::new("A", 3)], vec![Transfer::new("C", 3)]),
+        );
+
+        let graph = Graph::new(&transactions);
+        let report = detect_conflicts(&transactions, &graph, &Confirmations::new());
+
+        assert!(report.conflicts.is_empty());
+
+        // Spends the remaining 3 of A concurrently to transaction 4, approving transaction 3
+        transactions.insert(
+            5,
+            Transaction::new(3, 3, 2)
+                .with_transfers(vec![Transfer::new("A", 3)], vec![Transfer::new("D", 3)]),
+        );
+
+        let graph = Graph::new(&transactions);
+        let report = detect_conflicts(&transactions, &graph, &Confirmations::new());
+
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].transactions, BTreeSet::from([4, 5]));
+    }
+}
@@ -0,0 +1,255 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::error::LedgerError;
+use super::graph::{Depth, Transactions};
+use super::layers::Layer;
+use super::transaction::{Timestamp, TxId, TxRecord};
+use super::{Ledger, Result};
+
+/// A transaction row of the CSV database, the parents are separated by spaces.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+struct CsvTx {
+    /// The transaction id.
+    id: TxId,
+    /// The transaction's parents separated by spaces.
+    parents: String,
+    /// The transaction's timestamp.
+    timestamp: Timestamp,
+}
+
+/// A transaction row of the CSV database with the legacy `left` and `right` columns.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+struct LegacyCsvTx {
+    /// The transaction id.
+    id: TxId,
+    /// The transaction's left parent.
+    left: TxId,
+    /// The transaction's right parent.
+    right: TxId,
+    /// The transaction's timestamp.
+    timestamp: Timestamp,
+}
+
+/// A transaction row with either the parents column or the legacy `left` and `right` columns.
+#[derive(Deserialize)]
+struct RawCsvTx {
+    id: TxId,
+    #[serde(default)]
+    left: Option<TxId>,
+    #[serde(default)]
+    right: Option<TxId>,
+    #[serde(default)]
+    parents: Option<String>,
+    timestamp: Timestamp,
+}
+
+impl TryFrom<RawCsvTx> for TxRecord {
+    type Error = LedgerError;
+
+    fn try_from(raw: RawCsvTx) -> Result<Self> {
+        let parents = raw
+            .parents
+            .iter()
+            .flat_map(|parents| parents.split_whitespace())
+            .map(|parent| parent.parse::<TxId>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            id: raw.id,
+            parents: raw
+                .left
+                .into_iter()
+                .chain(raw.right)
+                .chain(parents)
+                .collect(),
+            timestamp: raw.timestamp,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        })
+    }
+}
+
+/// The computed fields of a transaction exported to CSV.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+struct TxFields {
+    /// The transaction id or its original hash.
+    id: String,
+    /// The transaction timestamp, transaction 1 has no timestamp.
+    timestamp: Option<Timestamp>,
+    /// The transaction depth, unsolid transactions have no depth.
+    depth: Option<Depth>,
+    /// The number of parents in the ledger.
+    out_degree: usize,
+    /// The number of transactions directly approving the transaction.
+    in_degree: usize,
+    /// The number of referenced parents which are not in the ledger.
+    missing_parents: usize,
+}
+
+/// Reads the provided CSV database with the `id,parents,timestamp` header and returns a transactions list.
+/// The legacy `id,left,right,timestamp` header is supported too.
+pub fn read_txs_from_csv(database: &str, delimiter: u8) -> Result<Transactions> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .trim(::csv::Trim::All)
+        .from_reader(database.as_bytes());
+
+    let records = reader.deserialize::<RawCsvTx>().map(|record| {
+        record
+            .map_err(|e| LedgerError::CsvError(e.to_string()))
+            .and_then(TxRecord::try_from)
+    });
+
+    super::txs_from_records(records)
+}
+
+/// Writes the provided transactions list in the CSV format, ordered by id.
+/// The `id,left,right,timestamp` header is kept when every transaction has two parents,
+/// otherwise the `id,parents,timestamp` header is written.
+/// The value transfers can't be represented in the CSV format.
+pub fn write_txs_to_csv(transactions: &Transactions, delimiter: u8) -> Result<String> {
+    if let Some((id, _)) = transactions.iter().find(|(_, tx)| tx.has_transfers()) {
+        return Err(LedgerError::CsvError(format!(
+            "transaction {id} has value transfers"
+        )));
+    }
+
+    if transactions.values().all(|tx| tx.parents.len() == 2) {
+        let rows = super::records_from_txs(transactions).map(|record| LegacyCsvTx {
+            id: record.id,
+            left: record.parents[0],
+            right: record.parents[1],
+            timestamp: record.timestamp,
+        });
+
+        return write_csv(rows, delimiter);
+    }
+
+    let rows = super::records_from_txs(transactions).map(|record| CsvTx {
+        id: record.id,
+        parents: record.parents.iter().join(" "),
+        timestamp: record.timestamp,
+    });
+
+    write_csv(rows, delimiter)
+}
+
+/// Writes the computed fields of every transaction in the ledger in the CSV format, ordered by id.
+pub fn write_tx_fields_to_csv(ledger: &Ledger, delimiter: u8) -> Result<String> {
+    let fields = (1..=ledger.graph.size())
+        .filter_map(|id| ledger.transaction(id))
+        .map(|info| TxFields {
+            id: ledger.display_id(info.id),
+            timestamp: info.timestamp,
+            depth: info.depth,
+            out_degree: info.parents.len(),
+            in_degree: info.approvers.len(),
+            missing_parents: info.missing_parents.len(),
+        });
+
+    write_csv(fields, delimiter)
+}
+
+/// Writes the per-depth layers in the CSV format, transaction 1 has empty timestamps.
+pub fn write_layers_to_csv(layers: &[Layer], delimiter: u8) -> Result<String> {
+    write_csv(layers.iter(), delimiter)
+}
+
+/// Writes the records with the header row in the CSV format.
+fn write_csv<T: Serialize>(records: impl Iterator<Item = T>, delimiter: u8) -> Result<String> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+
+    for record in records {
+        writer
+            .serialize(record)
+            .map_err(|e| LedgerError::CsvError(e.to_string()))?;
+    }
+
+    let csv = writer
+        .into_inner()
+        .map_err(|e| LedgerError::CsvError(e.to_string()))?;
+
+    Ok(String::from_utf8(csv).expect("the CSV output must be valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn read_with_delimiter() {
+        let database = "id;left;right;timestamp\n3; 1; 2; 0\n2;1;1;0\n";
+
+        let transactions = read_txs_from_csv(database, b';').unwrap();
+
+        assert_eq!(
+            transactions,
+            Transactions::from([
+                (2, Transaction::new(1, 1, 0)),
+                (3, Transaction::new(1, 2, 0)),
+            ])
+        );
+        assert_eq!(
+            write_txs_to_csv(&transactions, b'\t').unwrap(),
+            "id\tleft\tright\ttimestamp\n2\t1\t1\t0\n3\t1\t2\t0\n"
+        );
+    }
+
+    #[test]
+    fn read_parents_column() {
+        let database = "id,parents,timestamp\n2,1,0\n3,1 2 2,1\n";
+
+        let transactions = read_txs_from_csv(database, b',').unwrap();
+
+        assert_eq!(transactions[&2], Transaction::with_parents(vec![1], 0));
+        assert_eq!(
+            transactions[&3],
+            Transaction::with_parents(vec![1, 2, 2], 1)
+        );
+        assert_eq!(write_txs_to_csv(&transactions, b',').unwrap(), database);
+        assert_eq!(
+            read_txs_from_csv("id,parents,timestamp\n2,,0\n", b','),
+            Err(LedgerError::WrongParentsNumberError(0))
+        );
+    }
+
+    #[test]
+    fn read_invalid_records() {
+        assert_eq!(
+            read_txs_from_csv("id,left,right,timestamp\n", b','),
+            Err(LedgerError::EmptyDatabase)
+        );
+        assert_eq!(
+            read_txs_from_csv("id,left,right,timestamp\n2,1,1,0\n2,1,1,0\n", b','),
+            Err(LedgerError::DuplicateTxError(2))
+        );
+        assert!(matches!(
+            read_txs_from_csv("id,left,right,timestamp\n2,1,x,0\n", b','),
+            Err(LedgerError::CsvError(_))
+        ));
+    }
+
+    #[test]
+    fn write_computed_fields() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 0));
+        transactions.insert(4, Transaction::new(3, 7, 1));
+
+        let ledger = Ledger::new(transactions);
+
+        assert_eq!(
+            write_tx_fields_to_csv(&ledger, b',').unwrap(),
+            "id,timestamp,depth,out_degree,in_degree,missing_parents\n\
+             1,,0,0,2,0\n\
+             2,0,1,1,1,0\n\
+             3,0,1,2,1,0\n\
+             4,1,,1,0,1\n"
+        );
+    }
+}
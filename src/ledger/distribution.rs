@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// A summary of a values distribution.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Distribution {
+    /// The number of values.
+    pub count: usize,
+    /// The minimum value.
+    pub min: f64,
+    /// The maximum value.
+    pub max: f64,
+    /// The average value.
+    pub mean: f64,
+    /// The 50th percentile.
+    pub median: f64,
+    /// The 90th percentile.
+    pub p90: f64,
+    /// The 99th percentile.
+    pub p99: f64,
+}
+
+impl std::fmt::Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "count {}, min {}, mean {}, median {}, p90 {}, p99 {}, max {}",
+            self.count, self.min, self.mean, self.median, self.p90, self.p99, self.max
+        )
+    }
+}
+
+impl Distribution {
+    /// Creates a new `Distribution` instance from the provided values.
+    /// All the fields are zero if there are no values.
+    pub fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        values.sort_by(f64::total_cmp);
+
+        let count = values.len();
+
+        // Nearest-rank percentile
+        let percentile = |p: usize| values[((p * count).div_ceil(100)).max(1) - 1];
+
+        Self {
+            count,
+            min: values[0],
+            max: values[count - 1],
+            mean: values.iter().sum::<f64>() / count as f64,
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+        }
+    }
+
+    /// Creates a new `Distribution` instance from the number of occurrences of every value.
+    /// All the fields are zero if there are no values.
+    pub fn from_counts(counts: &BTreeMap<i64, usize>) -> Self {
+        let count = counts.values().sum::<usize>();
+
+        let (Some(min), Some(max)) = (counts.keys().next(), counts.keys().next_back()) else {
+            return Self::default();
+        };
+
+        // Nearest-rank percentile
+        let percentile = |p: usize| {
+            let rank = ((p * count).div_ceil(100)).max(1);
+            let mut seen = 0;
+
+            counts
+                .iter()
+                .find(|(_, n)| {
+                    seen += **n;
+                    seen >= rank
+                })
+                .map_or(0.0, |(value, _)| *value as f64)
+        };
+
+        Self {
+            count,
+            min: *min as f64,
+            max: *max as f64,
+            mean: counts
+                .iter()
+                .fold(0.0, |sum, (value, n)| sum + *value as f64 * *n as f64)
+                / count as f64,
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_distribution() {
+        assert_eq!(Distribution::new([]), Distribution::default());
+    }
+
+    #[test]
+    fn sample_distribution() {
+        let distribution = Distribution::new((1..=10).rev().map(|v| v as f64));
+
+        assert_eq!(distribution.count, 10);
+        assert_eq!(distribution.min, 1.0);
+        assert_eq!(distribution.max, 10.0);
+        assert_eq!(distribution.mean, 5.5);
+        assert_eq!(distribution.median, 5.0);
+        assert_eq!(distribution.p90, 9.0);
+        assert_eq!(distribution.p99, 10.0);
+    }
+
+    #[test]
+    fn distribution_from_counts() {
+        let counts = BTreeMap::from([(-1, 1), (2, 3), (5, 6)]);
+
+        assert_eq!(
+            Distribution::from_counts(&counts),
+            Distribution::new([-1.0, 2.0, 2.0, 2.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0])
+        );
+        assert_eq!(
+            Distribution::from_counts(&BTreeMap::new()),
+            Distribution::default()
+        );
+    }
+}
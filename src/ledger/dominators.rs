@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::distribution::Distribution;
+use super::graph::Graph;
+use super::transaction::TxId;
+
+/// The dominator tree of the solid transactions rooted at transaction 1.
+///
+/// A transaction dominates another one if every path of references from the other one
+/// to transaction 1 crosses it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dominators {
+    /// The immediate dominator of every solid transaction, transaction 1 has none.
+    pub idoms: BTreeMap<TxId, TxId>,
+    /// The dominance frontier of every transaction with a non-empty frontier.
+    pub frontiers: BTreeMap<TxId, BTreeSet<TxId>>,
+    /// The number of strictly dominated transactions of every transaction dominating any.
+    pub dominated: BTreeMap<TxId, usize>,
+    /// The dominance frontier sizes distribution of the solid transactions.
+    pub frontier_sizes: Distribution,
+    /// The dominator tree depths distribution of the solid transactions.
+    pub tree_depths: Distribution,
+}
+
+impl Dominators {
+    /// Returns the transactions other than transaction 1 which dominate any transaction,
+    /// ordered by the descending number of dominated transactions.
+    pub fn bottlenecks(&self) -> Vec<(TxId, usize)> {
+        let mut bottlenecks = self
+            .dominated
+            .iter()
+            .filter(|(id, _)| **id != 1)
+            .map(|(id, dominated)| (*id, *dominated))
+            .collect::<Vec<_>>();
+
+        bottlenecks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        bottlenecks
+    }
+}
+
+/// Calculates the dominator tree of the solid transactions and the dominance frontiers.
+pub fn calculate_dominators(graph: &Graph) -> Dominators {
+    let order = graph.topological_order();
+
+    let mut idoms = BTreeMap::new();
+    let mut tree_depths = HashMap::from([(1, 0)]);
+
+    // The graph is acyclic, so the parents are final when a transaction is processed
+    // in the topological order and a single pass is enough.
+    for id in order.iter().copied().filter(|id| *id != 1) {
+        let idom = graph
+            .parents(id)
+            .reduce(|a, b| intersect(&idoms, &tree_depths, a, b))
+            .expect("a solid transaction must have parents");
+
+        idoms.insert(id, idom);
+        tree_depths.insert(id, tree_depths[&idom] + 1);
+    }
+
+    let mut frontiers = BTreeMap::<TxId, BTreeSet<TxId>>::new();
+
+    for id in idoms.keys() {
+        let parents = graph.parents(*id).collect::<Vec<_>>();
+
+        if parents.len() < 2 {
+            continue;
+        }
+
+        for mut runner in parents {
+            while runner != idoms[id] {
+                frontiers.entry(runner).or_default().insert(*id);
+                runner = idoms[&runner];
+            }
+        }
+    }
+
+    let mut subtree_sizes = order.iter().map(|id| (*id, 1)).collect::<HashMap<_, _>>();
+
+    for id in order.iter().rev() {
+        if let Some(idom) = idoms.get(id) {
+            let size = subtree_sizes[id];
+            *subtree_sizes
+                .get_mut(idom)
+                .expect("the dominator must be solid") += size;
+        }
+    }
+
+    Dominators {
+        dominated: subtree_sizes
+            .into_iter()
+            .filter(|(_, size)| *size > 1)
+            .map(|(id, size)| (id, size - 1))
+            .collect(),
+        frontier_sizes: Distribution::new(
+            order
+                .iter()
+                .map(|id| frontiers.get(id).map_or(0, |f| f.len()) as f64),
+        ),
+        tree_depths: Distribution::new(tree_depths.values().map(|d| *d as f64)),
+        idoms,
+        frontiers,
+    }
+}
+
+/// Returns the nearest common dominator of the transactions by walking up the dominator tree.
+fn intersect(
+    idoms: &BTreeMap<TxId, TxId>,
+    tree_depths: &HashMap<TxId, usize>,
+    mut a: TxId,
+    mut b: TxId,
+) -> TxId {
+    while a != b {
+        if tree_depths[&a] >= tree_depths[&b] {
+            a = idoms[&a];
+        } else {
+            b = idoms[&b];
+        }
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Transactions;
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn sample_dominators() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(2, 2, 1));
+        transactions.insert(4, Transaction::new(2, 2, 1));
+        transactions.insert(5, Transaction::new(3, 4, 2));
+        transactions.insert(6, Transaction::new(5, 1, 3));
+        transactions.insert(7, Transaction::new(9, 9, 4));
+
+        let graph = Graph::new(&transactions);
+        let dominators = calculate_dominators(&graph);
+
+        assert_eq!(
+            dominators.idoms,
+            BTreeMap::from([(2, 1), (3, 2), (4, 2), (5, 2), (6, 1)])
+        );
+        assert_eq!(
+            dominators.frontiers,
+            BTreeMap::from([
+                (2, BTreeSet::from([6])),
+                (3, BTreeSet::from([5])),
+                (4, BTreeSet::from([5])),
+                (5, BTreeSet::from([6])),
+            ])
+        );
+        assert_eq!(dominators.dominated, BTreeMap::from([(1, 5), (2, 3)]));
+        assert_eq!(dominators.bottlenecks(), vec![(2, 3)]);
+        assert_eq!(dominators.frontier_sizes.max, 1.0);
+        assert_eq!(dominators.tree_depths.max, 2.0);
+    }
+}
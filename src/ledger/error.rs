@@ -5,10 +5,41 @@ use thiserror::Error;
 pub enum LedgerError {
     #[error("the database is empty")]
     EmptyDatabase,
+    #[error("utf-8 error: {0}")]
+    Utf8Error(#[from] std::str::Utf8Error),
     #[error("parse int error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("parse transaction error: {0}")]
     ParseTxError(#[from] super::transaction::ParseTxError),
+    #[error(
+        "wrong parents number: expected 1 to {}, actual '{0}'",
+        super::MAX_PARENTS
+    )]
+    WrongParentsNumberError(usize),
+    #[error("invalid parent: '{0}'")]
+    InvalidParentError(super::TxId),
+    #[error("missing transaction: '{0}'")]
+    MissingTxError(super::TxId),
+    #[error("invalid transaction id: '{0}'")]
+    InvalidTxIdError(super::TxId),
+    #[error("duplicate transaction: '{0}'")]
+    DuplicateTxError(super::TxId),
+    #[error("parse json error at line {0}: {1}")]
+    ParseJsonError(usize, String),
+    #[error("binary format error: {0}")]
+    BinaryFormatError(String),
+    #[error("checksum mismatch: expected '{0}', actual '{1}'")]
+    ChecksumError(u32, u32),
+    #[error("invalid transaction hash: '{0}'")]
+    InvalidHashError(String),
+    #[error("duplicate transaction hash: '{0}'")]
+    DuplicateHashError(String),
+    #[error("missing transaction hash: '{0}'")]
+    MissingHashError(String),
+    #[error("csv error: {0}")]
+    CsvError(String),
     #[error("wrong transactions number: expected '{0}', actual '{1}")]
     WrongTxNumberError(usize, usize),
+    #[error("transaction '{0}' is timestamped {2} before its parent '{1}'")]
+    TimestampViolationError(super::TxId, super::TxId, super::Timestamp),
 }
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::TxId;
+
+/// How far back a transaction reaches to one of its parents.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParentGap {
+    /// The parent id.
+    pub parent: TxId,
+    /// The difference between the transaction id and the parent id.
+    pub id_gap: i64,
+    /// The difference between the transaction timestamp and the parent timestamp,
+    /// transaction 1 has no timestamp.
+    pub timestamp_gap: Option<i64>,
+    /// The difference between the transaction depth and the parent depth,
+    /// unsolid transactions have no depth.
+    pub depth_gap: Option<i64>,
+}
+
+/// The distributions of the gaps of all the parent references.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ParentGapStats {
+    /// The id gaps distribution.
+    pub id: Distribution,
+    /// The timestamp gaps distribution.
+    pub timestamp: Distribution,
+    /// The depth gaps distribution.
+    pub depth: Distribution,
+}
+
+impl ParentGapStats {
+    /// Creates a new `ParentGapStats` instance from the gaps of every transaction.
+    pub fn new(gaps: &BTreeMap<TxId, Vec<ParentGap>>) -> Self {
+        let gaps = || gaps.values().flatten();
+
+        Self {
+            id: Distribution::new(gaps().map(|g| g.id_gap as f64)),
+            timestamp: Distribution::new(gaps().filter_map(|g| g.timestamp_gap.map(|t| t as f64))),
+            depth: Distribution::new(gaps().filter_map(|g| g.depth_gap.map(|d| d as f64))),
+        }
+    }
+}
+
+/// Returns the gaps to the parents of every transaction in the parents order, duplicate parents are counted once.
+pub fn calculate_parent_gaps(
+    transactions: &Transactions,
+    graph: &Graph,
+) -> BTreeMap<TxId, Vec<ParentGap>> {
+    let depths = graph.depths();
+
+    transactions
+        .iter()
+        .map(|(id, tx)| {
+            let gaps = tx
+                .parents
+                .iter()
+                .copied()
+                .unique()
+                .map(|parent| ParentGap {
+                    parent,
+                    id_gap: *id as i64 - parent as i64,
+                    timestamp_gap: transactions
+                        .get(&parent)
+                        .map(|p| tx.timestamp as i64 - p.timestamp as i64),
+                    depth_gap: depths
+                        .get(id)
+                        .zip(depths.get(&parent))
+                        .map(|(d, p)| *d as i64 - *p as i64),
+                })
+                .collect();
+
+            (*id, gaps)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn parent_gaps() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 4));
+        transactions.insert(4, Transaction::new(2, 3, 5));
+        transactions.insert(5, Transaction::new(4, 7, 9));
+
+        let graph = Graph::new(&transactions);
+        let gaps = calculate_parent_gaps(&transactions, &graph);
+
+        assert_eq!(
+            gaps[&3],
+            vec![
+                ParentGap {
+                    parent: 1,
+                    id_gap: 2,
+                    timestamp_gap: None,
+                    depth_gap: Some(1),
+                },
+                ParentGap {
+                    parent: 2,
+                    id_gap: 1,
+                    timestamp_gap: Some(4),
+                    depth_gap: Some(0),
+                },
+            ]
+        );
+        assert_eq!(gaps[&5][1].depth_gap, None);
+
+        let stats = ParentGapStats::new(&gaps);
+
+        assert_eq!(stats.id.count, 7);
+        assert_eq!(stats.id.max, 2.0);
+        assert_eq!(stats.timestamp.count, 4);
+        assert_eq!(stats.timestamp.max, 5.0);
+        assert_eq!(stats.depth.count, 5);
+    }
+}
@@ -0,0 +1,91 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::graph::Transactions;
+use super::simulation::{Simulation, SimulationConfig};
+
+/// The synthetic ledger generator configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    /// The number of transactions to generate(transaction 1 is not included).
+    pub transactions: usize,
+    /// The average number of transactions per timestamp.
+    pub arrival_rate: f64,
+    /// The tangle growth parameters.
+    pub simulation: SimulationConfig,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            transactions: 1000,
+            arrival_rate: 10.0,
+            simulation: SimulationConfig::default(),
+        }
+    }
+}
+
+/// Generates a transactions list with Poisson arrival timestamps.
+/// The same configuration always produces the same transactions list.
+pub fn generate_txs(config: &GeneratorConfig) -> Transactions {
+    let mut rng = ChaCha8Rng::seed_from_u64(config.simulation.seed);
+    rng.set_stream(1);
+
+    let mut simulation = Simulation::new(config.simulation.clone());
+    let mut now = 0.0;
+
+    for _ in 0..config.transactions {
+        now += -(1.0 - rng.gen::<f64>()).ln() / config.arrival_rate;
+
+        simulation.issue(now);
+    }
+
+    simulation.into_transactions()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::simulation::TipSelection;
+    use super::*;
+
+    #[test]
+    fn generated_ledger_is_valid() {
+        let config = GeneratorConfig {
+            transactions: 200,
+            ..Default::default()
+        };
+
+        let transactions = generate_txs(&config);
+
+        assert_eq!(transactions.len(), 200);
+
+        for (id, tx) in &transactions {
+            assert_eq!(tx.parents.len(), 2);
+
+            for &parent in &tx.parents {
+                assert!(parent >= 1 && parent < *id);
+
+                if parent != 1 {
+                    assert!(transactions[&parent].timestamp <= tx.timestamp);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_ledger() {
+        let mut config = GeneratorConfig {
+            transactions: 100,
+            ..Default::default()
+        };
+        config.simulation.tip_selection = TipSelection::RandomWalk { alpha: 0.5 };
+        config.simulation.seed = 42;
+
+        assert_eq!(generate_txs(&config), generate_txs(&config));
+
+        let mut other = config.clone();
+        other.simulation.seed = 43;
+
+        assert_ne!(generate_txs(&config), generate_txs(&other));
+    }
+}
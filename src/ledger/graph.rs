@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 use itertools::Itertools;
 
@@ -10,6 +10,10 @@ pub type Depth = usize;
 pub type Depths = HashMap<TxId, Depth>;
 /// Type alias for transactions list.
 pub type Transactions = HashMap<TxId, Transaction>;
+/// Type alias for approvers list.
+pub type Approvers = BTreeSet<TxId>;
+/// Type alias for missing parents list.
+pub type MissingParents = BTreeSet<TxId>;
 
 /// Type alias for adjacency matrix.
 type AdjacencyMatrix = HashMap<TxId, HashMap<TxId, Element>>;
@@ -29,14 +33,65 @@ impl Element {
     }
 }
 
+/// A set of topological positions, stored from the word of the smallest one.
+struct PositionSet {
+    /// The index of the first stored word.
+    offset: usize,
+    /// The position bits.
+    words: Vec<u64>,
+}
+
+impl PositionSet {
+    /// Creates a new `PositionSet` instance with the position.
+    fn new(position: usize) -> Self {
+        Self {
+            offset: position / 64,
+            words: vec![1 << (position % 64)],
+        }
+    }
+
+    /// Adds the positions of the other set, its smallest position must not be below this one.
+    fn union(&mut self, other: &Self) {
+        let start = other.offset - self.offset;
+
+        if self.words.len() < start + other.words.len() {
+            self.words.resize(start + other.words.len(), 0);
+        }
+
+        for (word, other) in self.words[start..].iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Returns the number of positions in the set.
+    fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
 /// A transactions graph implementation.
+///
+/// A transaction is solid if all its parents are in the graph and are solid too.
+/// Transaction 1 is always solid, only solid transactions have depths.
 pub struct Graph {
     /// The adjacency matrix size.
     size: usize,
+    /// The ids of the transactions in the graph.
+    ids: HashSet<TxId>,
     /// The adjacency matrix.
     adjacency_matrix: AdjacencyMatrix,
+    /// The total number of references in the adjacency matrix.
+    references: u32,
+    /// The transactions approvers.
+    approvers: HashMap<TxId, Approvers>,
+    /// The references to the parents which are not in the graph yet.
+    missing: AdjacencyMatrix,
+    /// The transactions waiting for the missing parents.
+    waiting: HashMap<TxId, BTreeSet<TxId>>,
     /// The information about depths.
     depths: Depths,
+    /// The longest paths to transaction 1 of the solid transactions.
+    levels: Depths,
 }
 
 impl std::fmt::Debug for Graph {
@@ -67,29 +122,42 @@ impl std::fmt::Debug for Graph {
         writeln!(f)?;
         writeln!(f, "Depths:")?;
 
-        for (i, depth) in self.depths.iter().sorted_by_key(|(i, _)| *i) {
-            if *i != size {
-                writeln!(f, "{} - {}", i, depth)?;
-            } else {
-                write!(f, "{} - {}", i, depth)?;
-            }
-        }
+        let depths = self
+            .depths
+            .iter()
+            .sorted_by_key(|(i, _)| *i)
+            .map(|(i, depth)| format!("{i} - {depth}"))
+            .join("\n");
 
-        Ok(())
+        write!(f, "{depths}")
     }
 }
 
 impl Graph {
     /// Creates a new `Graph` instance.
     pub fn new(transactions: &Transactions) -> Self {
+        let mut ids = transactions.keys().copied().collect::<HashSet<_>>();
+        ids.insert(1);
+
         let mut graph = Self {
-            size: transactions.len() + 1,
+            size: ids
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or(1)
+                .max(transactions.len() + 1),
+            ids,
             adjacency_matrix: HashMap::new(),
+            references: 0,
+            approvers: HashMap::new(),
+            missing: AdjacencyMatrix::new(),
+            waiting: HashMap::new(),
             depths: Depths::new(),
+            levels: Depths::new(),
         };
 
         for (id, tx) in transactions {
-            graph.add_ref(*id, tx.left, tx.right);
+            graph.add_ref(*id, &tx.parents);
         }
 
         graph.calculate_depths();
@@ -102,15 +170,9 @@ impl Graph {
         self.size
     }
 
-    /// Calls the function for each element in the adjacency matrix.
-    /// The elements will be provided in random order.
-    pub fn for_each<F>(&self, mut f: F)
-    where
-        F: FnMut(&TxId, &TxId, &Element),
-    {
-        self.adjacency_matrix
-            .iter()
-            .for_each(|(i, map)| map.iter().for_each(|(j, e)| f(i, j, e)));
+    /// Returns the total number of references in the adjacency matrix.
+    pub fn references(&self) -> u32 {
+        self.references
     }
 
     /// Returns the element of the adjacency matrix by index if it exists.
@@ -129,16 +191,247 @@ impl Graph {
         &self.depths
     }
 
+    /// Returns the levels collection: the longest paths to transaction 1 of the solid transactions.
+    /// Unlike depths, levels strictly decrease along every reference.
+    pub fn levels(&self) -> &Depths {
+        &self.levels
+    }
+
+    /// Returns the transaction parents in random order.
+    pub fn parents(&self, id: TxId) -> impl Iterator<Item = TxId> + '_ {
+        self.adjacency_matrix
+            .get(&id)
+            .into_iter()
+            .flat_map(|entry| entry.keys().copied())
+    }
+
+    /// Returns the transaction approvers.
+    pub fn approvers(&self, id: TxId) -> Option<&Approvers> {
+        self.approvers.get(&id)
+    }
+
+    /// Checks if the transaction is in the graph.
+    pub fn contains(&self, id: TxId) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Returns the transactions without approvers in ascending order.
+    pub fn tips(&self) -> Vec<TxId> {
+        self.ids
+            .iter()
+            .copied()
+            .filter(|id| self.approvers(*id).is_none())
+            .sorted()
+            .collect()
+    }
+
+    /// Returns the solid transactions in topological order, parents before their approvers.
+    /// Transactions ready at the same time are ordered by id.
+    pub fn topological_order(&self) -> Vec<TxId> {
+        let mut pending = HashMap::new();
+        let mut ready = BTreeSet::from([1]);
+        let mut order = Vec::with_capacity(self.depths.len());
+
+        while let Some(j) = ready.pop_first() {
+            order.push(j);
+
+            for &i in self.approvers(j).into_iter().flatten() {
+                if !self.is_solid(i) {
+                    continue;
+                }
+
+                let counter = pending
+                    .entry(i)
+                    .or_insert_with(|| self.adjacency_matrix[&i].len());
+                *counter -= 1;
+
+                if *counter == 0 {
+                    ready.insert(i);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Returns the cumulative weights of the solid transactions: the number of solid transactions
+    /// directly or indirectly approving the transaction plus one.
+    pub fn cumulative_weights(&self) -> HashMap<TxId, u64> {
+        let order = self.topological_order();
+
+        let mut weights = HashMap::with_capacity(order.len());
+        let mut cones = HashMap::<TxId, PositionSet>::new();
+        let mut pending = HashMap::new();
+
+        // The future cones are merged in one reverse topological pass, the cone of a transaction
+        // is dropped as soon as all its parents have merged it
+        for (position, &id) in order.iter().enumerate().rev() {
+            let mut cone = PositionSet::new(position);
+
+            for approver in self.approvers(id).into_iter().flatten() {
+                let Some(counter) = pending.get_mut(approver) else {
+                    continue;
+                };
+
+                cone.union(&cones[approver]);
+
+                *counter -= 1;
+
+                if *counter == 0 {
+                    pending.remove(approver);
+                    cones.remove(approver);
+                }
+            }
+
+            weights.insert(id, cone.len() as u64);
+
+            if let Some(parents) = self.adjacency_matrix.get(&id) {
+                pending.insert(id, parents.len());
+                cones.insert(id, cone);
+            }
+        }
+
+        weights
+    }
+
+    /// Returns all the transactions directly or indirectly approved by the transaction.
+    pub fn past_cone(&self, id: TxId) -> BTreeSet<TxId> {
+        self.cone(id, |i| self.parents(i).collect())
+    }
+
+    /// Returns all the transactions directly or indirectly approving the transaction.
+    pub fn future_cone(&self, id: TxId) -> BTreeSet<TxId> {
+        self.cone(id, |i| {
+            self.approvers(i).into_iter().flatten().copied().collect()
+        })
+    }
+
+    /// Checks if the transaction is solid.
+    pub fn is_solid(&self, id: TxId) -> bool {
+        self.depths.contains_key(&id)
+    }
+
+    /// Checks if all the transactions in the graph are solid.
+    pub fn is_solid_graph(&self) -> bool {
+        self.depths.len() == self.ids.len()
+    }
+
+    /// Returns the number of transactions in the graph including transaction 1.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns the referenced parents which are not in the graph yet.
+    pub fn missing_parents(&self, id: TxId) -> Option<MissingParents> {
+        self.missing
+            .get(&id)
+            .map(|entry| entry.keys().copied().collect())
+    }
+
+    /// Adds the transaction to the graph.
+    /// The transactions waiting for it and the transaction itself become solid if their past cones are complete.
+    /// Returns the transactions which became solid, their depths are calculated.
+    pub fn insert(&mut self, id: TxId, tx: &Transaction) -> Vec<TxId> {
+        debug_assert!(
+            !self.ids.contains(&id),
+            "the transaction is already in the graph"
+        );
+
+        self.size = self.size.max(id);
+        self.ids.insert(id);
+
+        self.add_ref(id, &tx.parents);
+
+        for child in self.waiting.remove(&id).into_iter().flatten() {
+            let entry = self
+                .missing
+                .get_mut(&child)
+                .expect("the child must be waiting");
+            let element = entry
+                .remove(&id)
+                .expect("the child must reference the parent");
+
+            if entry.is_empty() {
+                self.missing.remove(&child);
+            }
+
+            self.references += element.references;
+            self.adjacency_matrix
+                .entry(child)
+                .or_default()
+                .insert(id, element);
+            self.approvers.entry(id).or_default().insert(child);
+        }
+
+        self.solidify(id)
+    }
+
     /// Adds the element-related references.
-    fn add_ref(&mut self, id: TxId, left: TxId, right: TxId) {
-        if !self.is_valid_index(id) || !self.is_valid_index(left) || !self.is_valid_index(right) {
+    /// References to the parents which are not in the graph are kept as missing.
+    fn add_ref(&mut self, id: TxId, parents: &[TxId]) {
+        if !self.is_valid_index(id) {
             return;
         }
 
-        let entry = self.adjacency_matrix.entry(id).or_insert(HashMap::new());
+        for &parent in parents {
+            if self.ids.contains(&parent) {
+                let entry = self.adjacency_matrix.entry(id).or_default();
+                entry.entry(parent).or_default().references += 1;
+
+                self.references += 1;
 
-        entry.entry(left).or_insert(Element::default()).references += 1;
-        entry.entry(right).or_insert(Element::default()).references += 1;
+                self.approvers.entry(parent).or_default().insert(id);
+            } else {
+                let entry = self.missing.entry(id).or_default();
+                entry.entry(parent).or_default().references += 1;
+
+                self.waiting.entry(parent).or_default().insert(id);
+            }
+        }
+    }
+
+    /// Marks the transaction and its approvers as solid if their past cones are complete.
+    /// Returns the transactions which became solid.
+    fn solidify(&mut self, id: TxId) -> Vec<TxId> {
+        let mut solidified = Vec::new();
+        let mut queue = VecDeque::from([id]);
+
+        while let Some(i) = queue.pop_front() {
+            if self.is_solid(i) || self.missing.contains_key(&i) {
+                continue;
+            }
+
+            let parents = self.parents(i).collect::<Vec<_>>();
+
+            if !parents.is_empty() && parents.iter().all(|j| self.is_solid(*j)) {
+                self.insert_depth(i, &parents);
+                solidified.push(i);
+
+                queue.extend(self.approvers(i).into_iter().flatten());
+            }
+        }
+
+        solidified
+    }
+
+    /// Returns all the transactions reachable from the transaction by the provided edges.
+    /// The transaction itself is not included.
+    fn cone<F>(&self, id: TxId, next: F) -> BTreeSet<TxId>
+    where
+        F: Fn(TxId) -> Vec<TxId>,
+    {
+        let mut cone = BTreeSet::new();
+        let mut stack = vec![id];
+
+        while let Some(i) = stack.pop() {
+            for j in next(i) {
+                if j != id && cone.insert(j) {
+                    stack.push(j);
+                }
+            }
+        }
+
+        cone
     }
 
     /// Checks if the provided index is valid.
@@ -146,44 +439,69 @@ impl Graph {
         index > 0 && index <= self.size()
     }
 
-    /// Calculates the depths.
+    /// Calculates the depths of the solid transactions.
     /// The adjacency matrix must be already built.
     fn calculate_depths(&mut self) {
-        let mut path = HashMap::new();
+        let mut pending = self
+            .adjacency_matrix
+            .iter()
+            .map(|(i, entry)| (*i, entry.len()))
+            .collect::<HashMap<_, _>>();
+
         let mut queue = VecDeque::new();
 
         let start = 1;
 
-        path.insert(start, start);
+        self.depths.insert(start, 0);
+        self.levels.insert(start, 0);
         queue.push_back(start);
 
-        while !queue.is_empty() {
-            let j = queue.pop_front().expect("the queue can not be empty");
+        // A transaction becomes solid when the last of its parents becomes solid,
+        // its depth is the shortest path to transaction 1.
+        while let Some(j) = queue.pop_front() {
+            let approvers = self.approvers(j).cloned().unwrap_or_default();
+
+            for i in approvers {
+                if self.is_solid(i) || self.missing.contains_key(&i) {
+                    continue;
+                }
+
+                let counter = pending.get_mut(&i).expect("the approver must have parents");
+                *counter -= 1;
+
+                if *counter == 0 {
+                    let parents = self.parents(i).collect::<Vec<_>>();
 
-            for i in 1..=self.size() {
-                if !path.contains_key(&i) && self.get(i, j).is_some() {
                     queue.push_back(i);
-                    *path.entry(i).or_default() = j;
+                    self.insert_depth(i, &parents);
                 }
             }
         }
+    }
 
-        for i in 1..=self.size() {
-            let mut depth = 0;
-            let mut j = i;
-
-            while j != 1 {
-                depth += 1;
-                j = *path.get(&j).expect("the path must be valid");
-            }
+    /// Marks the transaction as solid, its depth is the shortest path to transaction 1
+    /// and its level is the longest one. All the parents must be solid.
+    fn insert_depth(&mut self, id: TxId, parents: &[TxId]) {
+        let depth = parents
+            .iter()
+            .map(|p| self.depths[p])
+            .min()
+            .expect("the transaction must have parents");
+        let level = parents
+            .iter()
+            .map(|p| self.levels[p])
+            .max()
+            .expect("the transaction must have parents");
 
-            *self.depths.entry(i).or_default() = depth;
-        }
+        self.depths.insert(id, depth + 1);
+        self.levels.insert(id, level + 1);
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
+    use super::super::generator::{generate_txs, GeneratorConfig};
     use super::*;
 
     #[test]
@@ -234,6 +552,10 @@ mod tests {
             *graph.depths(),
             Depths::from([(1, 0), (2, 1), (3, 1), (4, 2), (5, 2), (6, 2)])
         );
+        assert_eq!(
+            *graph.levels(),
+            Depths::from([(1, 0), (2, 1), (3, 2), (4, 2), (5, 3), (6, 3)])
+        );
 
         assert_eq!(graph.is_valid_index(0), false);
         assert_eq!(graph.is_valid_index(1), true);
@@ -248,15 +570,155 @@ mod tests {
         assert_eq!(graph.size(), 1);
         assert_eq!(graph.get(1, 1), None);
 
-        graph.add_ref(0, 1, 1);
-        graph.add_ref(2, 1, 1);
+        graph.add_ref(0, &[1, 1]);
+        graph.add_ref(2, &[1, 1]);
 
         assert_eq!(graph.size(), 1);
         assert_eq!(graph.get(1, 1), None);
 
-        graph.add_ref(1, 1, 1);
+        graph.add_ref(1, &[1, 1]);
 
         assert_eq!(graph.size(), 1);
         assert_eq!(graph.get(1, 1).unwrap(), &Element::new(2));
+        assert_eq!(graph.approvers(1), Some(&Approvers::from([1])));
+    }
+
+    #[test]
+    fn variable_parents() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::with_parents(vec![1], 0));
+        transactions.insert(3, Transaction::with_parents(vec![1, 2, 2], 0));
+        transactions.insert(4, Transaction::with_parents(vec![3, 2, 1, 9], 1));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(graph.references(), 7);
+        assert_eq!(graph.get(3, 2).unwrap(), &Element::new(2));
+        assert_eq!(graph.missing_parents(4), Some(MissingParents::from([9])));
+        assert_eq!(*graph.depths(), Depths::from([(1, 0), (2, 1), (3, 1)]));
+    }
+
+    #[test]
+    fn insert_to_graph() {
+        let mut graph = Graph::new(&Transactions::new());
+
+        graph.insert(2, &Transaction::new(1, 1, 0));
+        graph.insert(3, &Transaction::new(1, 2, 0));
+        graph.insert(4, &Transaction::new(2, 2, 1));
+
+        assert_eq!(graph.size(), 4);
+        assert_eq!(graph.get(3, 1).unwrap(), &Element::new(1));
+        assert_eq!(graph.get(4, 2).unwrap(), &Element::new(2));
+
+        assert_eq!(graph.approvers(1), Some(&Approvers::from([2, 3])));
+        assert_eq!(graph.approvers(2), Some(&Approvers::from([3, 4])));
+        assert_eq!(graph.approvers(3), None);
+        assert_eq!(graph.parents(3).sorted().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.tips(), vec![3, 4]);
+
+        assert_eq!(graph.past_cone(4), BTreeSet::from([1, 2]));
+        assert_eq!(graph.past_cone(1), BTreeSet::new());
+        assert_eq!(graph.future_cone(1), BTreeSet::from([2, 3, 4]));
+        assert_eq!(graph.future_cone(3), BTreeSet::new());
+
+        assert_eq!(
+            *graph.depths(),
+            Depths::from([(1, 0), (2, 1), (3, 1), (4, 2)])
+        );
+    }
+
+    #[test]
+    fn missing_parents() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 7, 0));
+        transactions.insert(4, Transaction::new(3, 2, 1));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(graph.size(), 4);
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.get(3, 1).unwrap(), &Element::new(1));
+        assert_eq!(graph.get(4, 3).unwrap(), &Element::new(1));
+
+        assert_eq!(graph.is_solid(2), true);
+        assert_eq!(graph.is_solid(3), false);
+        assert_eq!(graph.is_solid(4), false);
+        assert_eq!(graph.is_solid_graph(), false);
+
+        assert_eq!(graph.missing_parents(3), Some(MissingParents::from([7])));
+        assert_eq!(graph.missing_parents(4), None);
+
+        assert_eq!(*graph.depths(), Depths::from([(1, 0), (2, 1)]));
+    }
+
+    #[test]
+    fn solidify_with_later_parent() {
+        let mut graph = Graph::new(&Transactions::new());
+
+        assert_eq!(
+            graph.insert(3, &Transaction::new(2, 2, 1)),
+            Vec::<TxId>::new()
+        );
+        assert_eq!(
+            graph.insert(4, &Transaction::new(3, 1, 1)),
+            Vec::<TxId>::new()
+        );
+
+        assert_eq!(graph.size(), 4);
+        assert_eq!(graph.missing_parents(3), Some(MissingParents::from([2])));
+        assert_eq!(graph.get(3, 2), None);
+
+        assert_eq!(graph.insert(2, &Transaction::new(1, 1, 0)), vec![2, 3, 4]);
+
+        assert_eq!(graph.is_solid_graph(), true);
+        assert_eq!(graph.missing_parents(3), None);
+        assert_eq!(graph.get(3, 2).unwrap(), &Element::new(2));
+        assert_eq!(graph.approvers(2), Some(&Approvers::from([3])));
+
+        assert_eq!(
+            *graph.depths(),
+            Depths::from([(1, 0), (2, 1), (3, 2), (4, 1)])
+        );
+        assert_eq!(
+            *graph.levels(),
+            Depths::from([(1, 0), (2, 1), (3, 2), (4, 3)])
+        );
+    }
+
+    #[test]
+    fn cumulative_weights() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 0));
+        transactions.insert(4, Transaction::new(2, 2, 1));
+        transactions.insert(5, Transaction::new(3, 7, 2));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(
+            graph.cumulative_weights(),
+            HashMap::from([(1, 4), (2, 3), (3, 1), (4, 1)])
+        );
+    }
+
+    #[test]
+    fn cumulative_weights_of_generated_ledger() {
+        let config = GeneratorConfig {
+            transactions: 300,
+            ..Default::default()
+        };
+
+        let graph = Graph::new(&generate_txs(&config));
+        let weights = graph.cumulative_weights();
+
+        assert_eq!(weights.len(), 301);
+
+        for (id, weight) in weights {
+            assert_eq!(weight, graph.future_cone(id).len() as u64 + 1);
+        }
     }
 }
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use super::error::LedgerError;
+use super::graph::Transactions;
+use super::transaction::{is_valid_parents_number, ParseTxError, Timestamp, Transaction, TxId};
+use super::Result;
+
+/// The interned transaction hashes, mapped to dense transaction ids.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TxHashes {
+    /// The hashes indexed by transaction id, id 0 is not used.
+    hashes: Vec<String>,
+    /// The transaction ids by hash.
+    ids: HashMap<String, TxId>,
+}
+
+impl TxHashes {
+    /// Creates a new `TxHashes` instance with the hash of transaction 1.
+    pub fn new(genesis: &str) -> Result<Self> {
+        let mut hashes = Self {
+            hashes: vec![String::new()],
+            ids: HashMap::new(),
+        };
+
+        hashes.intern(genesis)?;
+
+        Ok(hashes)
+    }
+
+    /// Returns the id of the hash, assigning the next id to a new hash.
+    pub fn intern(&mut self, hash: &str) -> Result<TxId> {
+        if let Some(id) = self.ids.get(hash) {
+            return Ok(*id);
+        }
+
+        if !is_valid_hash(hash) {
+            return Err(LedgerError::InvalidHashError(hash.to_owned()));
+        }
+
+        let id = self.hashes.len();
+
+        self.hashes.push(hash.to_owned());
+        self.ids.insert(hash.to_owned(), id);
+
+        Ok(id)
+    }
+
+    /// Assigns the next id to a transaction without a hash, so that it is never given to a hash.
+    pub(super) fn reserve(&mut self) -> TxId {
+        self.hashes.push(String::new());
+
+        self.hashes.len() - 1
+    }
+
+    /// Returns the id of the hash if it is interned.
+    pub fn id(&self, hash: &str) -> Option<TxId> {
+        self.ids.get(hash).copied()
+    }
+
+    /// Returns the hash of the transaction id if it is interned.
+    pub fn hash(&self, id: TxId) -> Option<&str> {
+        // Id 0 and the reserved ids have no hash
+        self.hashes
+            .get(id)
+            .filter(|hash| !hash.is_empty())
+            .map(String::as_str)
+    }
+
+    /// Reads the provided milestones list of hashes, one per line, and returns the milestone ids.
+    pub fn read_milestones(&self, milestones: &str) -> Result<Vec<TxId>> {
+        milestones
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                self.id(l)
+                    .ok_or_else(|| LedgerError::MissingHashError(l.to_owned()))
+            })
+            .collect()
+    }
+
+    /// Returns the number of assigned ids, interned or reserved.
+    pub fn len(&self) -> usize {
+        self.hashes.len() - 1
+    }
+
+    /// Returns true if there are no interned hashes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns true if the hash is a hexadecimal or a tryte(`9A-Z`) string.
+fn is_valid_hash(hash: &str) -> bool {
+    !hash.is_empty()
+        && hash
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() || c.is_ascii_uppercase())
+}
+
+/// Reads the provided hashed database and returns a transactions list and the interned hashes.
+///
+/// The first line is the hash of transaction 1, every other line is a `hash parent... timestamp` transaction.
+/// Transactions get the ids 2, 3, ... in line order, parents which are not in the database get the following ids.
+pub fn read_hashed_txs_from_db(database: &str) -> Result<(Transactions, TxHashes)> {
+    let mut lines = database.lines();
+
+    let genesis = lines.next().ok_or(LedgerError::EmptyDatabase)?;
+    let mut hashes = TxHashes::new(genesis.trim())?;
+
+    let mut records = Vec::new();
+
+    for line in lines {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let parents_number = fields.len().saturating_sub(2);
+
+        if !is_valid_parents_number(parents_number) {
+            return Err(LedgerError::ParseTxError(
+                ParseTxError::WrongParentsNumberError(parents_number),
+            ));
+        }
+
+        let (hash, parents, timestamp) = (
+            fields[0],
+            &fields[1..fields.len() - 1],
+            fields[fields.len() - 1],
+        );
+
+        let id = hashes.len() + 1;
+
+        if hashes.intern(hash)? != id {
+            return Err(LedgerError::DuplicateHashError(hash.to_owned()));
+        }
+
+        records.push((id, parents.to_vec(), timestamp.parse::<Timestamp>()?));
+    }
+
+    let mut transactions = Transactions::with_capacity(records.len());
+
+    for (id, parents, timestamp) in records {
+        let parents = parents
+            .into_iter()
+            .map(|parent| hashes.intern(parent))
+            .collect::<Result<_>>()?;
+
+        transactions.insert(id, Transaction::with_parents(parents, timestamp));
+    }
+
+    Ok((transactions, hashes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_hashes() {
+        let mut hashes = TxHashes::new("999").unwrap();
+
+        assert_eq!(hashes.intern("ABC9"), Ok(2));
+        assert_eq!(hashes.intern("00ff"), Ok(3));
+        assert_eq!(hashes.intern("ABC9"), Ok(2));
+        assert_eq!(
+            hashes.intern("abc-"),
+            Err(LedgerError::InvalidHashError("abc-".to_owned()))
+        );
+
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(hashes.id("999"), Some(1));
+        assert_eq!(hashes.hash(3), Some("00ff"));
+        assert_eq!(hashes.hash(0), None);
+        assert_eq!(hashes.hash(4), None);
+
+        assert_eq!(hashes.reserve(), 4);
+        assert_eq!(hashes.intern("EE"), Ok(5));
+        assert_eq!(hashes.hash(4), None);
+
+        assert_eq!(hashes.read_milestones("ABC9\n\n00ff\n"), Ok(vec![2, 3]));
+        assert_eq!(
+            hashes.read_milestones("FF"),
+            Err(LedgerError::MissingHashError("FF".to_owned()))
+        );
+    }
+
+    #[test]
+    fn read_hashed_database() {
+        let database = "GENESIS\nAA 0xff GENESIS 0\nBB GENESIS AA 1\nCC BB DD 2\n";
+
+        assert_eq!(
+            read_hashed_txs_from_db(database),
+            Err(LedgerError::InvalidHashError("0xff".to_owned()))
+        );
+
+        let database = database.replace("0xff", "CC");
+        let (transactions, hashes) = read_hashed_txs_from_db(&database).unwrap();
+
+        assert_eq!(
+            transactions,
+            Transactions::from([
+                (2, Transaction::new(4, 1, 0)),
+                (3, Transaction::new(1, 2, 1)),
+                (4, Transaction::new(3, 5, 2)),
+            ])
+        );
+        assert_eq!(hashes.hash(5), Some("DD"));
+
+        assert_eq!(
+            read_hashed_txs_from_db("GENESIS\nAA GENESIS GENESIS 0\nAA AA AA 1"),
+            Err(LedgerError::DuplicateHashError("AA".to_owned()))
+        );
+        assert_eq!(
+            read_hashed_txs_from_db("GENESIS\nAA GENESIS BB GENESIS 0\nBB 1"),
+            Err(LedgerError::ParseTxError(
+                ParseTxError::WrongParentsNumberError(0)
+            ))
+        );
+        assert_eq!(
+            read_hashed_txs_from_db("GENESIS\nAA GENESIS BB GENESIS 0\nBB AA 1")
+                .unwrap()
+                .0[&2],
+            Transaction::with_parents(vec![1, 3, 1], 0)
+        );
+        assert_eq!(read_hashed_txs_from_db(""), Err(LedgerError::EmptyDatabase));
+    }
+}
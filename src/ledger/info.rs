@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use super::graph::{Approvers, Depth, MissingParents};
+use super::transaction::{Timestamp, TxId};
+
+/// The information about a single transaction in the ledger.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TxInfo {
+    /// The transaction id.
+    pub id: TxId,
+    /// The original transaction hash of a hashed database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// The transaction timestamp, transaction 1 has no timestamp.
+    pub timestamp: Option<Timestamp>,
+    /// The transaction depth, unsolid transactions have no depth.
+    pub depth: Option<Depth>,
+    /// The parents in the ledger.
+    pub parents: Vec<TxId>,
+    /// The referenced parents which are not in the ledger.
+    pub missing_parents: MissingParents,
+    /// The transactions directly approving the transaction.
+    pub approvers: Approvers,
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+
+use super::error::LedgerError;
+use super::graph::Transactions;
+use super::transaction::{Timestamp, TxId, TxRecord};
+use super::value::Transfer;
+use super::Result;
+
+/// A transaction object with the legacy `left` and `right` fields.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+struct LegacyJsonTx {
+    /// The transaction id.
+    id: TxId,
+    /// The transaction's left parent.
+    left: TxId,
+    /// The transaction's right parent.
+    right: TxId,
+    /// The transaction's timestamp.
+    timestamp: Timestamp,
+    /// The values spent from the addresses.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inputs: Vec<Transfer>,
+    /// The values sent to the addresses.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    outputs: Vec<Transfer>,
+}
+
+/// Reads the provided JSON Lines database and returns a transactions list.
+/// Every line is a transaction object with an explicit id, empty lines are skipped.
+pub fn read_txs_from_jsonl(database: &str) -> Result<Transactions> {
+    let records = database
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<TxRecord>(line)
+                .map_err(|e| LedgerError::ParseJsonError(i + 1, e.to_string()))
+        });
+
+    super::txs_from_records(records)
+}
+
+/// Writes the provided transactions list in the JSON Lines database format, ordered by id.
+/// The `left` and `right` fields are kept when every transaction has two parents,
+/// otherwise every object has the `parents` field.
+pub fn write_txs_to_jsonl(transactions: &Transactions) -> Result<String> {
+    if transactions.values().all(|tx| tx.parents.len() == 2) {
+        let objects = super::records_from_txs(transactions).map(|record| LegacyJsonTx {
+            id: record.id,
+            left: record.parents[0],
+            right: record.parents[1],
+            timestamp: record.timestamp,
+            inputs: record.inputs,
+            outputs: record.outputs,
+        });
+
+        return Ok(write_jsonl(objects));
+    }
+
+    Ok(write_jsonl(super::records_from_txs(transactions)))
+}
+
+/// Writes the objects one per line.
+fn write_jsonl<T: Serialize>(objects: impl Iterator<Item = T>) -> String {
+    let mut database = String::new();
+
+    for object in objects {
+        database.push_str(
+            &serde_json::to_string(&object).expect("the transaction object must be serializable"),
+        );
+        database.push('\n');
+    }
+
+    database
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn read_explicit_ids() {
+        let database = r#"{"id":3,"left":1,"right":2,"timestamp":0}
+
+{"id":2,"left":1,"right":1,"timestamp":0}
+"#;
+
+        let transactions = read_txs_from_jsonl(database).unwrap();
+
+        assert_eq!(
+            transactions,
+            Transactions::from([
+                (2, Transaction::new(1, 1, 0)),
+                (3, Transaction::new(1, 2, 0)),
+            ])
+        );
+        assert_eq!(
+            write_txs_to_jsonl(&transactions).unwrap(),
+            "{\"id\":2,\"left\":1,\"right\":1,\"timestamp\":0}\n\
+             {\"id\":3,\"left\":1,\"right\":2,\"timestamp\":0}\n"
+        );
+    }
+
+    #[test]
+    fn write_variable_parents() {
+        let transactions = Transactions::from([
+            (2, Transaction::new(1, 1, 0)),
+            (3, Transaction::with_parents(vec![1, 2, 2], 1)),
+        ]);
+
+        let database = write_txs_to_jsonl(&transactions).unwrap();
+
+        assert_eq!(
+            database,
+            "{\"id\":2,\"parents\":[1,1],\"timestamp\":0}\n\
+             {\"id\":3,\"parents\":[1,2,2],\"timestamp\":1}\n"
+        );
+        assert_eq!(read_txs_from_jsonl(&database).unwrap(), transactions);
+    }
+
+    #[test]
+    fn read_invalid_records() {
+        assert_eq!(read_txs_from_jsonl("\n"), Err(LedgerError::EmptyDatabase));
+        assert_eq!(
+            read_txs_from_jsonl(r#"{"id":1,"left":1,"right":1,"timestamp":0}"#),
+            Err(LedgerError::InvalidTxIdError(1))
+        );
+        assert_eq!(
+            read_txs_from_jsonl(
+                r#"{"id":2,"left":1,"right":1,"timestamp":0}
+{"id":2,"left":1,"right":1,"timestamp":1}"#
+            ),
+            Err(LedgerError::DuplicateTxError(2))
+        );
+        assert!(matches!(
+            read_txs_from_jsonl(r#"{"id":2,"left":1}"#),
+            Err(LedgerError::ParseJsonError(1, _))
+        ));
+    }
+}
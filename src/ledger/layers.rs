@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::graph::{Depth, Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+
+/// The solid transactions at the same depth.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Layer {
+    /// The layer depth, transaction 1 is at depth 0.
+    pub depth: Depth,
+    /// The number of transactions.
+    pub transactions: usize,
+    /// The earliest timestamp, transaction 1 has no timestamp.
+    pub min_timestamp: Option<Timestamp>,
+    /// The latest timestamp, transaction 1 has no timestamp.
+    pub max_timestamp: Option<Timestamp>,
+    /// The average number of approvers.
+    pub avg_in_degree: f64,
+    /// The number of transactions without approvers.
+    pub tips: usize,
+}
+
+/// Returns the layers of the solid transactions ordered by depth.
+pub fn calculate_layers(transactions: &Transactions, graph: &Graph) -> Vec<Layer> {
+    let mut layers = BTreeMap::<Depth, Vec<TxId>>::new();
+
+    for (id, depth) in graph.depths() {
+        layers.entry(*depth).or_default().push(*id);
+    }
+
+    layers
+        .into_iter()
+        .map(|(depth, ids)| {
+            let timestamps = ids
+                .iter()
+                .filter_map(|id| transactions.get(id).map(|tx| tx.timestamp));
+            let in_degrees = ids
+                .iter()
+                .map(|id| graph.approvers(*id).map_or(0, |a| a.len()))
+                .collect::<Vec<_>>();
+
+            Layer {
+                depth,
+                transactions: ids.len(),
+                min_timestamp: timestamps.clone().min(),
+                max_timestamp: timestamps.max(),
+                avg_in_degree: in_degrees.iter().sum::<usize>() as f64 / ids.len() as f64,
+                tips: in_degrees.iter().filter(|d| **d == 0).count(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    #[test]
+    fn sample_layers() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 1));
+        transactions.insert(4, Transaction::new(2, 3, 2));
+        transactions.insert(5, Transaction::new(3, 8, 3));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(
+            calculate_layers(&transactions, &graph),
+            vec![
+                Layer {
+                    depth: 0,
+                    transactions: 1,
+                    min_timestamp: None,
+                    max_timestamp: None,
+                    avg_in_degree: 2.0,
+                    tips: 0,
+                },
+                Layer {
+                    depth: 1,
+                    transactions: 2,
+                    min_timestamp: Some(0),
+                    max_timestamp: Some(1),
+                    avg_in_degree: 2.0,
+                    tips: 0,
+                },
+                Layer {
+                    depth: 2,
+                    transactions: 1,
+                    min_timestamp: Some(2),
+                    max_timestamp: Some(2),
+                    avg_in_degree: 0.0,
+                    tips: 1,
+                },
+            ]
+        );
+    }
+}
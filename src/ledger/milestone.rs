@@ -0,0 +1,89 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::confirmation::Confirmations;
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, TxId};
+use super::{LedgerError, Result};
+
+/// A coordinator milestone and the transactions it confirms.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Milestone {
+    /// The milestone index, starting from 1.
+    pub index: usize,
+    /// The milestone transaction id.
+    pub id: TxId,
+    /// The milestone transaction timestamp.
+    pub timestamp: Timestamp,
+    /// The time passed since the previous milestone.
+    pub interval: Option<Timestamp>,
+    /// The transactions confirmed by this milestone but not by the previous ones.
+    /// Contains the milestone transaction itself, transaction 1 is not included.
+    pub confirmed: BTreeSet<TxId>,
+}
+
+/// Reads the provided milestones list and returns the milestone transaction ids in index order.
+/// The list contains one transaction id per line.
+pub fn read_milestones(milestones: &str) -> Result<Vec<TxId>> {
+    milestones
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.parse::<TxId>().map_err(LedgerError::ParseIntError))
+        .collect()
+}
+
+/// Calculates the transactions confirmed by every milestone.
+pub fn calculate_milestones(
+    transactions: &Transactions,
+    graph: &Graph,
+    milestones: &[TxId],
+) -> Vec<Milestone> {
+    let mut confirmed = HashSet::from([1]);
+    let mut previous: Option<Timestamp> = None;
+
+    milestones
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let timestamp = transactions[id].timestamp;
+
+            // The confirmed transactions set is closed under ancestry,
+            // so the search stops at the transactions confirmed by the previous milestones.
+            let mut cone = BTreeSet::new();
+            let mut stack = Vec::new();
+
+            if confirmed.insert(*id) {
+                cone.insert(*id);
+                stack.push(*id);
+            }
+
+            while let Some(j) = stack.pop() {
+                for parent in graph.parents(j) {
+                    if confirmed.insert(parent) {
+                        cone.insert(parent);
+                        stack.push(parent);
+                    }
+                }
+            }
+
+            let interval = previous.map(|p| timestamp.saturating_sub(p));
+            previous = Some(timestamp);
+
+            Milestone {
+                index: i + 1,
+                id: *id,
+                timestamp,
+                interval,
+                confirmed: cone,
+            }
+        })
+        .collect()
+}
+
+/// Returns the confirmation timestamps defined by the milestones.
+pub fn milestone_confirmations(milestones: &[Milestone]) -> Confirmations {
+    milestones
+        .iter()
+        .flat_map(|m| m.confirmed.iter().map(|id| (*id, m.timestamp)))
+        .collect()
+}
@@ -1,6 +1,28 @@
+mod audit;
+mod binary;
+mod causality;
+mod confirmation;
+mod conflict;
+mod csv;
+mod distribution;
+mod dominators;
 mod error;
+mod gaps;
+mod generator;
 mod graph;
+mod hashes;
+mod info;
+#[cfg(feature = "jsonl")]
+mod jsonl;
+mod layers;
+mod milestone;
+mod parallel;
+mod paths;
+mod simulation;
+mod stats;
+mod stream;
 mod transaction;
+mod value;
 
 use itertools::Itertools;
 
@@ -9,12 +31,40 @@ pub use self::error::LedgerError;
 /// Type alias for the ledger module result.
 pub type Result<T> = std::result::Result<T, LedgerError>;
 
-use self::graph::{Depth, Graph};
-
-pub use self::graph::Transactions;
-pub use self::transaction::{ParseTxError, Timestamp, Transaction, TxId};
-
-use std::collections::HashMap;
+use self::graph::Graph;
+use self::stats::RunningStats;
+
+pub use self::audit::{AuditConfig, AuditReport, LazyApproval, ParasiteChain};
+pub use self::binary::{
+    read_txs_from_binary, write_txs_to_binary, BinaryLedger, BINARY_MAGIC, BINARY_VERSION,
+};
+pub use self::causality::{TimestampCheck, TimestampReport, TimestampViolation};
+pub use self::confirmation::{ConfirmationReport, ConfirmationRule, Confirmations};
+pub use self::conflict::{Conflict, ConflictReport, ConflictResolution};
+pub use self::csv::{
+    read_txs_from_csv, write_layers_to_csv, write_tx_fields_to_csv, write_txs_to_csv,
+};
+pub use self::distribution::Distribution;
+pub use self::dominators::Dominators;
+pub use self::gaps::{ParentGap, ParentGapStats};
+pub use self::generator::{generate_txs, GeneratorConfig};
+pub use self::graph::{Approvers, Depth, MissingParents, Transactions};
+pub use self::hashes::{read_hashed_txs_from_db, TxHashes};
+pub use self::info::TxInfo;
+#[cfg(feature = "jsonl")]
+pub use self::jsonl::{read_txs_from_jsonl, write_txs_to_jsonl};
+pub use self::layers::Layer;
+pub use self::milestone::{read_milestones, Milestone};
+pub use self::parallel::par_read_txs_from_db;
+pub use self::simulation::{replay_txs, Simulation, SimulationConfig, TipSelection};
+pub use self::stats::Stats;
+pub use self::stream::TxStream;
+pub use self::transaction::{ParseTxError, Timestamp, Transaction, TxId, MAX_PARENTS};
+pub use self::value::{Address, Amount, Balances, Transfer};
+
+use self::transaction::TxRecord;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// A ledger implementation.
 pub struct Ledger {
@@ -22,6 +72,12 @@ pub struct Ledger {
     transactions: Transactions,
     /// The transactions graph.
     graph: Graph,
+    /// The milestone transaction ids in index order.
+    milestones: Vec<TxId>,
+    /// The statistics updated on every new transaction.
+    stats: RunningStats,
+    /// The original transaction hashes of a hashed database.
+    hashes: Option<TxHashes>,
 }
 
 impl std::fmt::Debug for Ledger {
@@ -35,13 +91,38 @@ impl std::fmt::Debug for Ledger {
         writeln!(f, "{transactions_number}")?;
 
         for (i, tx) in self.transactions.iter().sorted_by_key(|(i, _)| *i) {
-            writeln!(f, "{} - {} {} {}", i, tx.left, tx.right, tx.timestamp)?;
+            let parents = tx.parents.iter().map(|p| self.display_id(*p)).join(" ");
+
+            writeln!(f, "{} - {} {}", self.display_id(*i), parents, tx.timestamp)?;
         }
 
         // Print graph debug information
         writeln!(f)?;
         writeln!(f, "{:?}", self.graph)?;
 
+        // Print unsolid transactions debug information
+        if !self.graph.is_solid_graph() {
+            writeln!(f)?;
+            writeln!(f, "Unsolid:")?;
+
+            for i in self
+                .transactions
+                .keys()
+                .sorted()
+                .filter(|i| !self.graph.is_solid(**i))
+            {
+                let missing = self
+                    .graph
+                    .missing_parents(*i)
+                    .into_iter()
+                    .flatten()
+                    .map(|p| self.display_id(p))
+                    .join(" ");
+
+                writeln!(f, "{} - {}", self.display_id(*i), missing)?;
+            }
+        }
+
         write!(f, "--------------------------------")
     }
 }
@@ -51,41 +132,204 @@ impl Ledger {
     pub fn new(transactions: Transactions) -> Self {
         let graph = Graph::new(&transactions);
 
+        let mut stats = RunningStats::default();
+
+        transactions.values().for_each(|tx| stats.add_tx(tx));
+        graph.depths().values().for_each(|d| stats.add_depth(*d));
+
+        for gap in gaps::calculate_parent_gaps(&transactions, &graph)
+            .values()
+            .flatten()
+        {
+            stats.add_id_gap(gap.id_gap);
+
+            if let Some(timestamp_gap) = gap.timestamp_gap {
+                stats.add_timestamp_gap(timestamp_gap);
+            }
+
+            if let Some(depth_gap) = gap.depth_gap {
+                stats.add_depth_gap(depth_gap);
+            }
+        }
+
         Self {
             transactions,
             graph,
+            milestones: Vec::new(),
+            stats,
+            hashes: None,
+        }
+    }
+
+    /// Adds the transaction with the next id and updates the statistics.
+    /// Returns the id assigned to the transaction.
+    pub fn add_transaction(&mut self, tx: Transaction) -> Result<TxId> {
+        let id = self.next_id();
+
+        if !transaction::is_valid_parents_number(tx.parents.len()) {
+            return Err(LedgerError::WrongParentsNumberError(tx.parents.len()));
+        }
+
+        if let Some(parent) = tx.parents.iter().copied().find(|p| *p == 0 || *p == id) {
+            return Err(LedgerError::InvalidParentError(parent));
         }
+
+        if let Some(hashes) = &mut self.hashes {
+            hashes.reserve();
+        }
+
+        self.stats.add_tx(&tx);
+
+        for parent in tx.parents.iter().unique() {
+            self.stats.add_id_gap(id as i64 - *parent as i64);
+        }
+
+        let solid = self.graph.insert(id, &tx);
+
+        // The timestamp gaps of the references to the present parents and of the earlier references to the transaction
+        for parent in self.graph.parents(id) {
+            if let Some(p) = self.transactions.get(&parent) {
+                self.stats
+                    .add_timestamp_gap(tx.timestamp as i64 - p.timestamp as i64);
+            }
+        }
+
+        for child in self.graph.approvers(id).into_iter().flatten() {
+            if let Some(c) = self.transactions.get(child) {
+                self.stats
+                    .add_timestamp_gap(c.timestamp as i64 - tx.timestamp as i64);
+            }
+        }
+
+        // The parents of a solid transaction are solid, so its depth gaps are final
+        for solid in solid {
+            let depth = self.graph.depths()[&solid];
+
+            self.stats.add_depth(depth);
+
+            for parent in self.graph.parents(solid) {
+                self.stats
+                    .add_depth_gap(depth as i64 - self.graph.depths()[&parent] as i64);
+            }
+        }
+
+        self.transactions.insert(id, tx);
+
+        Ok(id)
     }
 
-    /// Returns the average depth of the directed acyclic graph.
+    /// Returns the id of the next added transaction.
+    /// The ids interned for the missing parents of a hashed ledger follow the transactions ids and are skipped.
+    fn next_id(&self) -> TxId {
+        let hashes = self.hashes.as_ref().map_or(0, TxHashes::len);
+
+        self.graph.size().max(hashes) + 1
+    }
+
+    /// Sets the original transaction hashes reported instead of the transaction ids.
+    pub fn set_hashes(&mut self, hashes: TxHashes) {
+        self.hashes = Some(hashes);
+    }
+
+    /// Returns the original transaction hashes of a hashed database.
+    pub fn hashes(&self) -> Option<&TxHashes> {
+        self.hashes.as_ref()
+    }
+
+    /// Returns the original hash of the transaction or its id.
+    pub fn display_id(&self, id: TxId) -> String {
+        match self.hashes.as_ref().and_then(|hashes| hashes.hash(id)) {
+            Some(hash) => hash.to_owned(),
+            None => id.to_string(),
+        }
+    }
+
+    /// Returns the id of the transaction from its original hash or its id.
+    pub fn resolve_id(&self, id: &str) -> Option<TxId> {
+        match &self.hashes {
+            Some(hashes) => hashes.id(id),
+            None => id.parse::<TxId>().ok(),
+        }
+    }
+
+    /// Sets the milestone transaction ids in index order.
+    pub fn set_milestones(&mut self, milestones: Vec<TxId>) -> Result<()> {
+        if let Some(id) = milestones
+            .iter()
+            .find(|id| !self.transactions.contains_key(id))
+        {
+            return Err(LedgerError::MissingTxError(*id));
+        }
+
+        self.milestones = milestones;
+
+        Ok(())
+    }
+
+    /// Returns the milestones and the transactions newly confirmed by each of them.
+    pub fn milestones(&self) -> Vec<Milestone> {
+        milestone::calculate_milestones(&self.transactions, &self.graph, &self.milestones)
+    }
+
+    /// Returns the confirmation report where transactions are confirmed by the milestones.
+    pub fn milestone_confirmations(&self) -> ConfirmationReport {
+        let confirmations = milestone::milestone_confirmations(&self.milestones());
+
+        ConfirmationReport::new(&self.transactions, confirmations)
+    }
+
+    /// Returns the average depth of the directed acyclic graph(unsolid transactions are not included).
     pub fn avg_dag_depth(&self) -> f32 {
-        self.graph.depths().values().sum::<Depth>() as f32 / self.graph.size() as f32
+        self.stats.depths_sum as f32 / self.graph.depths().len() as f32
     }
 
     /// Returns the average number of transactions per depth(depth 0 is not included).
     pub fn avg_txs_per_depth(&self) -> f32 {
-        let depths = self.graph.depths();
-
-        let max_depth = depths
-            .values()
-            .max()
-            .expect("the depths collection can not be empty");
-
-        match max_depth {
+        match self.stats.max_depth {
             0 => 0.0,
-            _ => depths.values().filter(|d| **d != 0).count() as f32 / *max_depth as f32,
+            // Only transaction 1 has depth 0
+            max_depth => (self.graph.depths().len() - 1) as f32 / max_depth as f32,
         }
     }
 
+    /// Returns the number of solid transactions(transaction 1 is not included).
+    pub fn solid_txs_number(&self) -> usize {
+        self.graph.depths().len() - 1
+    }
+
+    /// Returns the number of transactions with incomplete past cones.
+    pub fn unsolid_txs_number(&self) -> usize {
+        self.graph.len() - self.graph.depths().len()
+    }
+
+    /// Returns the number of orphan transactions, referencing parents which are not in the ledger.
+    pub fn orphan_txs_number(&self) -> usize {
+        self.transactions
+            .keys()
+            .filter(|id| {
+                self.graph
+                    .missing_parents(**id)
+                    .is_some_and(|parents| !parents.is_empty())
+            })
+            .count()
+    }
+
+    /// Returns the transactions with incomplete past cones and their missing parents.
+    /// Transactions with only unsolid parents have no missing parents.
+    pub fn unsolid_txs(&self) -> BTreeMap<TxId, MissingParents> {
+        self.transactions
+            .keys()
+            .filter(|id| !self.graph.is_solid(**id))
+            .map(|id| (*id, self.graph.missing_parents(*id).unwrap_or_default()))
+            .collect()
+    }
+
     /// Returns the average number of in-references per node.
     pub fn avg_ref(&self) -> f32 {
         // TODO: is it possible to have nodes without connection to the graph?
         // (self.transactions.len() * 2) as f32 / self.graph.size() as f32
 
-        let mut sum = 0;
-        self.graph.for_each(|_, _, e| sum += e.references);
-
-        sum as f32 / self.graph.size() as f32
+        self.graph.references() as f32 / self.graph.size() as f32
     }
 
     /// Returns the average number of transactions per timestamp(transaction 1 is not included).
@@ -94,12 +338,154 @@ impl Ledger {
             return 0.0;
         }
 
-        let mut counter = HashMap::new();
-        self.transactions
-            .values()
-            .for_each(|t| *counter.entry(t.timestamp).or_default() += 1);
+        self.transactions.len() as f32 / self.stats.timestamps.len() as f32
+    }
+
+    /// Returns the information about the transaction if it is in the ledger.
+    pub fn transaction(&self, id: TxId) -> Option<TxInfo> {
+        if !self.graph.contains(id) {
+            return None;
+        }
+
+        Some(TxInfo {
+            id,
+            hash: self
+                .hashes
+                .as_ref()
+                .and_then(|hashes| hashes.hash(id))
+                .map(String::from),
+            timestamp: self.transactions.get(&id).map(|tx| tx.timestamp),
+            depth: self.graph.depths().get(&id).copied(),
+            parents: self.graph.parents(id).sorted().collect(),
+            missing_parents: self.graph.missing_parents(id).unwrap_or_default(),
+            approvers: self.graph.approvers(id).cloned().unwrap_or_default(),
+        })
+    }
+
+    /// Returns the transactions without approvers in ascending order.
+    pub fn tips(&self) -> Vec<TxId> {
+        self.graph.tips()
+    }
+
+    /// Returns all the transactions directly or indirectly approved by the transaction.
+    pub fn past_cone(&self, id: TxId) -> Option<BTreeSet<TxId>> {
+        self.graph.contains(id).then(|| self.graph.past_cone(id))
+    }
+
+    /// Returns all the transactions directly or indirectly approving the transaction.
+    pub fn future_cone(&self, id: TxId) -> Option<BTreeSet<TxId>> {
+        self.graph.contains(id).then(|| self.graph.future_cone(id))
+    }
+
+    /// Returns the snapshot of the ledger statistics.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            transactions: self.transactions.len(),
+            solid_transactions: self.solid_txs_number(),
+            unsolid_transactions: self.unsolid_txs_number(),
+            orphan_transactions: self.orphan_txs_number(),
+            tips: self.graph.tips().len(),
+            avg_dag_depth: self.avg_dag_depth(),
+            avg_txs_per_depth: self.avg_txs_per_depth(),
+            avg_ref: self.avg_ref(),
+            avg_txs_per_ts: self.avg_txs_per_ts(),
+            parent_gaps: self.stats.parent_gaps(),
+        }
+    }
+
+    /// Returns the heaviest path from transaction 1 to a solid tip, GHOST-style: every step follows
+    /// the solid approver with the greatest cumulative weight.
+    pub fn heaviest_path(&self) -> Vec<TxId> {
+        paths::heaviest_path(&self.graph)
+    }
+
+    /// Returns the shortest path from `from` to `to` following the parent references(only solid transactions have paths).
+    pub fn shortest_path(&self, from: TxId, to: TxId) -> Option<Vec<TxId>> {
+        paths::shortest_path(&self.graph, from, to)
+    }
+
+    /// Returns the longest path from `from` to `to` following the parent references(only solid transactions have paths).
+    pub fn longest_path(&self, from: TxId, to: TxId) -> Option<Vec<TxId>> {
+        paths::longest_path(&self.graph, from, to)
+    }
+
+    /// Returns the lowest common ancestors of the transactions, a transaction counts as its own ancestor(only solid transactions have common ancestors).
+    pub fn lowest_common_ancestors(&self, ids: &[TxId]) -> Option<BTreeSet<TxId>> {
+        paths::lowest_common_ancestors(&self.graph, ids)
+    }
+
+    /// Returns the dominator tree of the solid transactions rooted at transaction 1.
+    pub fn dominators(&self) -> Dominators {
+        dominators::calculate_dominators(&self.graph)
+    }
+
+    /// Returns the fraction of transactions within one hop of the path(transaction 1 is not included).
+    pub fn one_hop_fraction(&self, path: &[TxId]) -> f64 {
+        paths::one_hop_fraction(&self.transactions, &self.graph, path)
+    }
+
+    /// Returns the per-depth layers of the solid transactions.
+    pub fn layers(&self) -> Vec<Layer> {
+        layers::calculate_layers(&self.transactions, &self.graph)
+    }
+
+    /// Returns the id, timestamp and depth gaps to the parents of every transaction.
+    pub fn parent_gaps(&self) -> BTreeMap<TxId, Vec<ParentGap>> {
+        gaps::calculate_parent_gaps(&self.transactions, &self.graph)
+    }
+
+    /// Replays the transactions in timestamp order and returns when they become confirmed by the rule.
+    pub fn confirmations(&self, rule: ConfirmationRule) -> ConfirmationReport {
+        let confirmations =
+            confirmation::replay_confirmations(&self.transactions, &self.graph, rule);
+
+        ConfirmationReport::new(&self.transactions, confirmations)
+    }
 
-        counter.values().sum::<usize>() as f32 / counter.len() as f32
+    /// Detects the concurrent transactions double-spending from the same address and resolves them by the rule.
+    pub fn conflicts(&self, resolution: ConflictResolution) -> ConflictReport {
+        let confirmations = match resolution {
+            ConflictResolution::CumulativeWeight(threshold) => confirmation::replay_confirmations(
+                &self.transactions,
+                &self.graph,
+                ConfirmationRule::CumulativeWeight(threshold),
+            ),
+            ConflictResolution::Milestones => {
+                milestone::milestone_confirmations(&self.milestones())
+            }
+        };
+
+        conflict::detect_conflicts(&self.transactions, &self.graph, &confirmations)
+    }
+
+    /// Returns the transactions timestamped before their parents.
+    pub fn timestamp_violations(&self) -> TimestampReport {
+        causality::check_timestamps(&self.transactions, &self.graph)
+    }
+
+    /// Returns the timestamp violations, or an error for the largest one if the check is strict.
+    pub fn validate_timestamps(&self, check: TimestampCheck) -> Result<TimestampReport> {
+        self.timestamp_violations().check(check)
+    }
+
+    /// Returns the timestamp violations of the references from or to the provided transactions,
+    /// or an error for the largest one if the check is strict.
+    pub fn validate_new_timestamps(
+        &self,
+        ids: &BTreeSet<TxId>,
+        check: TimestampCheck,
+    ) -> Result<TimestampReport> {
+        causality::check_new_timestamps(&self.transactions, &self.graph, ids).check(check)
+    }
+
+    /// Detects the lazy approvals and the parasite chains attached to the old parts of the tangle.
+    pub fn audit(&self, config: &AuditConfig) -> AuditReport {
+        audit::audit(&self.transactions, &self.graph, config)
+    }
+
+    /// Applies the value transfers in topological order and returns the address balances.
+    pub fn balances(&self) -> Balances {
+        value::calculate_balances(&self.transactions, &self.graph)
     }
 }
 
@@ -143,3 +529,60 @@ pub fn read_txs_from_db(database: &str) -> Result<Transactions> {
 
     Ok(transactions)
 }
+
+/// Collects the transaction records with explicit ids into a transactions list.
+fn txs_from_records(records: impl Iterator<Item = Result<TxRecord>>) -> Result<Transactions> {
+    let mut transactions = Transactions::new();
+
+    for record in records {
+        let record = record?;
+
+        // Transaction 1 is implicit
+        if record.id < 2 {
+            return Err(LedgerError::InvalidTxIdError(record.id));
+        }
+
+        if !transaction::is_valid_parents_number(record.parents.len()) {
+            return Err(LedgerError::WrongParentsNumberError(record.parents.len()));
+        }
+
+        let tx = Transaction::with_parents(record.parents, record.timestamp)
+            .with_transfers(record.inputs, record.outputs);
+
+        if transactions.insert(record.id, tx).is_some() {
+            return Err(LedgerError::DuplicateTxError(record.id));
+        }
+    }
+
+    if transactions.is_empty() {
+        return Err(LedgerError::EmptyDatabase);
+    }
+
+    Ok(transactions)
+}
+
+/// Returns the transaction records with explicit ids ordered by id.
+fn records_from_txs(transactions: &Transactions) -> impl Iterator<Item = TxRecord> + '_ {
+    transactions
+        .iter()
+        .sorted_by_key(|(id, _)| **id)
+        .map(|(id, tx)| TxRecord::new(*id, tx))
+}
+
+/// Writes the provided transactions list in the database format.
+/// Transaction ids must be consecutive and start from 2.
+pub fn write_txs_to_db(transactions: &Transactions) -> Result<String> {
+    let transactions_number = transactions.len();
+
+    let mut database = format!("{transactions_number}\n");
+
+    for id in 2..transactions_number + 2 {
+        let tx = transactions
+            .get(&id)
+            .ok_or(LedgerError::MissingTxError(id))?;
+
+        database.push_str(&format!("{tx}\n"));
+    }
+
+    Ok(database)
+}
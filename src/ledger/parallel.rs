@@ -0,0 +1,131 @@
+use rayon::prelude::*;
+
+use super::error::LedgerError;
+use super::graph::Transactions;
+use super::transaction::Transaction;
+use super::Result;
+
+/// The minimum size of a chunk parsed by one thread.
+const MIN_CHUNK_SIZE: usize = 1 << 20;
+
+/// Reads the provided database in parallel and returns a transactions list, e.g. from a memory-mapped file.
+/// The transactions lines are split into chunks at newline boundaries, parsed in parallel
+/// and stitched back in order, so the ids are the same as the ones of `read_txs_from_db`.
+pub fn par_read_txs_from_db(database: &[u8]) -> Result<Transactions> {
+    let chunks_number =
+        (database.len() / MIN_CHUNK_SIZE).clamp(1, rayon::current_num_threads() * 4);
+
+    read_chunks(database, chunks_number)
+}
+
+/// Reads the provided database split into the provided number of chunks.
+fn read_chunks(database: &[u8], chunks_number: usize) -> Result<Transactions> {
+    if database.is_empty() {
+        return Err(LedgerError::EmptyDatabase);
+    }
+
+    let (header, body) = match database.iter().position(|b| *b == b'\n') {
+        Some(end) => (&database[..end], &database[end + 1..]),
+        None => (database, &database[database.len()..]),
+    };
+
+    let parsed_transactions_number = std::str::from_utf8(header)?
+        .trim_end_matches('\r')
+        .parse::<usize>()
+        .map_err(LedgerError::ParseIntError)?;
+
+    let chunks = split_chunks(body, chunks_number)
+        .into_par_iter()
+        .map(parse_chunk)
+        .collect::<Result<Vec<_>>>()?;
+
+    let transactions_number = chunks.iter().map(Vec::len).sum::<usize>();
+
+    if parsed_transactions_number != transactions_number {
+        return Err(LedgerError::WrongTxNumberError(
+            parsed_transactions_number,
+            transactions_number,
+        ));
+    }
+
+    let mut transactions = Transactions::with_capacity(transactions_number);
+
+    transactions.extend(
+        chunks
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, tx)| (i + 2, tx)),
+    );
+
+    Ok(transactions)
+}
+
+/// Splits the bytes into at most the provided number of chunks of whole lines.
+fn split_chunks(bytes: &[u8], chunks_number: usize) -> Vec<&[u8]> {
+    let chunk_size = bytes.len() / chunks_number.max(1) + 1;
+    let mut chunks = Vec::with_capacity(chunks_number);
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let end = (start + chunk_size).min(bytes.len());
+
+        // Extends the chunk to the end of its last line
+        let end = match bytes[end - 1..].iter().position(|b| *b == b'\n') {
+            Some(newline) => end + newline,
+            None => bytes.len(),
+        };
+
+        chunks.push(&bytes[start..end]);
+        start = end;
+    }
+
+    chunks
+}
+
+/// Parses every line of the chunk as a transaction.
+fn parse_chunk(chunk: &[u8]) -> Result<Vec<Transaction>> {
+    std::str::from_utf8(chunk)?
+        .lines()
+        .map(|l| l.parse::<Transaction>().map_err(LedgerError::ParseTxError))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_newlines() {
+        let bytes = b"1 1 0\n1 2 0\n2 2 1\n3 3 2\n";
+
+        for chunks_number in 1..=6 {
+            let chunks = split_chunks(bytes, chunks_number);
+
+            assert!(chunks.len() <= chunks_number);
+            assert_eq!(chunks.concat(), bytes);
+            assert!(chunks.iter().all(|chunk| chunk.ends_with(b"\n")));
+        }
+
+        assert_eq!(split_chunks(b"", 4), Vec::<&[u8]>::new());
+        assert_eq!(split_chunks(b"1 1 0", 4), vec![b"1 1 0"]);
+    }
+
+    #[test]
+    fn same_ids_as_sequential_read() {
+        let database = std::fs::read_to_string("./database.txt").unwrap();
+
+        for chunks_number in 1..=6 {
+            assert_eq!(
+                read_chunks(database.as_bytes(), chunks_number),
+                super::super::read_txs_from_db(&database)
+            );
+        }
+
+        assert_eq!(
+            read_chunks(b"3\n1 1 0\n1 2 0\n", 2),
+            Err(LedgerError::WrongTxNumberError(3, 2))
+        );
+        assert_eq!(read_chunks(b"", 2), Err(LedgerError::EmptyDatabase));
+    }
+}
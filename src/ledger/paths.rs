@@ -0,0 +1,389 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+
+use super::graph::{Graph, Transactions};
+use super::transaction::TxId;
+
+/// Returns the path from transaction 1 to a solid tip following the solid approver with the greatest
+/// cumulative weight at every step, ties are broken by the smallest id.
+pub fn heaviest_path(graph: &Graph) -> Vec<TxId> {
+    let weights = graph.cumulative_weights();
+    let mut path = vec![1];
+
+    // Solid transactions are acyclic, so the walk ends at a solid tip
+    while let Some(next) = graph
+        .approvers(path[path.len() - 1])
+        .into_iter()
+        .flatten()
+        .copied()
+        .filter(|approver| graph.is_solid(*approver))
+        .max_by_key(|approver| (weights[approver], Reverse(*approver)))
+    {
+        path.push(next);
+    }
+
+    path
+}
+
+/// Returns the shortest path from `from` to `to` following the parent references.
+/// Only solid transactions have paths, the past cone of a solid transaction is solid and acyclic.
+pub fn shortest_path(graph: &Graph, from: TxId, to: TxId) -> Option<Vec<TxId>> {
+    if !graph.is_solid(from) || !graph.is_solid(to) {
+        return None;
+    }
+
+    // Levels strictly decrease along the references, so only the transactions above `to` can lead to it.
+    // Depths can't prune the search, a parent may be closer to transaction 1 than `to` and still approve it.
+    let levels = graph.levels();
+    let leads_to = |i: &TxId| *i == to || levels[i] > levels[&to];
+
+    if !leads_to(&from) {
+        return None;
+    }
+
+    // The predecessor of every visited transaction on the shortest path from `from`
+    let mut predecessors = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(i) = queue.pop_front() {
+        if i == to {
+            return Some(unwind(&predecessors, from, to));
+        }
+
+        for parent in graph.parents(i).filter(leads_to) {
+            if let Entry::Vacant(e) = predecessors.entry(parent) {
+                e.insert(i);
+                queue.push_back(parent);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the longest path from `from` to `to` following the parent references.
+/// Only solid transactions have paths, the past cone of a solid transaction is solid and acyclic.
+pub fn longest_path(graph: &Graph, from: TxId, to: TxId) -> Option<Vec<TxId>> {
+    if !graph.is_solid(from) || !graph.is_solid(to) {
+        return None;
+    }
+
+    if from == to {
+        return Some(vec![from]);
+    }
+
+    // The transactions on the paths from `from` to `to`
+    let mut between = graph.past_cone(from);
+    if !between.contains(&to) {
+        return None;
+    }
+    let future_cone = graph.future_cone(to);
+    between.retain(|i| *i == to || future_cone.contains(i));
+    between.insert(from);
+
+    // A transaction is processed when all its approvers between `from` and `to` are processed,
+    // so its longest distance from `from` is final
+    let mut pending = between
+        .iter()
+        .map(|i| {
+            let approvers = graph.approvers(*i).into_iter().flatten();
+            (*i, approvers.filter(|a| between.contains(a)).count())
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut distances = HashMap::from([(from, 0)]);
+    let mut predecessors = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(i) = queue.pop_front() {
+        for parent in graph.parents(i).filter(|p| between.contains(p)) {
+            let distance = distances[&i] + 1;
+
+            if distances.get(&parent).is_none_or(|d| distance > *d) {
+                distances.insert(parent, distance);
+                predecessors.insert(parent, i);
+            }
+
+            let counter = pending
+                .get_mut(&parent)
+                .expect("the parent must be between");
+            *counter -= 1;
+
+            if *counter == 0 {
+                queue.push_back(parent);
+            }
+        }
+    }
+
+    Some(unwind(&predecessors, from, to))
+}
+
+/// Returns the lowest common ancestors of the transactions: the transactions approved directly or indirectly
+/// by all of them, a transaction counts as its own ancestor, which are not approved by another common ancestor.
+/// Only solid transactions have common ancestors.
+pub fn lowest_common_ancestors(graph: &Graph, ids: &[TxId]) -> Option<BTreeSet<TxId>> {
+    if ids.is_empty() || ids.iter().any(|id| !graph.is_solid(*id)) {
+        return None;
+    }
+
+    let levels = graph.levels();
+    let words = ids.len().div_ceil(64);
+    let common = |mask: &[u64]| (0..ids.len()).all(|bit| mask[bit / 64] & (1 << (bit % 64)) != 0);
+
+    // The inputs reaching every visited transaction
+    let mut masks = HashMap::<TxId, Vec<u64>>::new();
+
+    for (bit, id) in ids.iter().enumerate() {
+        masks.entry(*id).or_insert_with(|| vec![0; words])[bit / 64] |= 1 << (bit % 64);
+    }
+
+    // Levels strictly decrease along the references, so the highest level transaction is popped
+    // only after all its visited approvers and its mask is final
+    let mut frontier = masks
+        .keys()
+        .map(|id| (levels[id], *id))
+        .collect::<BinaryHeap<_>>();
+
+    // The common ancestors approved by another common ancestor, and the uncovered transactions in the frontier
+    let mut covered = HashSet::new();
+    let mut uncovered = frontier.len();
+
+    let mut ancestors = BTreeSet::new();
+
+    while uncovered > 0 {
+        let (_, i) = frontier
+            .pop()
+            .expect("the frontier must have uncovered transactions");
+        let mask = masks[&i].clone();
+        let is_common = common(&mask);
+
+        if !covered.contains(&i) {
+            uncovered -= 1;
+
+            if is_common {
+                ancestors.insert(i);
+            }
+        }
+
+        for parent in graph.parents(i) {
+            let entry = masks.entry(parent).or_insert_with(|| {
+                frontier.push((levels[&parent], parent));
+                uncovered += 1;
+
+                vec![0; words]
+            });
+
+            entry.iter_mut().zip(&mask).for_each(|(w, m)| *w |= m);
+
+            // The ancestors of a common ancestor are common ancestors too
+            if is_common && covered.insert(parent) {
+                uncovered -= 1;
+            }
+        }
+    }
+
+    Some(ancestors)
+}
+
+/// Returns the path from `from` to `to` by walking the predecessors back from `to`.
+fn unwind(predecessors: &HashMap<TxId, TxId>, from: TxId, to: TxId) -> Vec<TxId> {
+    let mut path = vec![to];
+
+    while path[path.len() - 1] != from {
+        path.push(predecessors[&path[path.len() - 1]]);
+    }
+
+    path.reverse();
+    path
+}
+
+/// Returns the fraction of transactions on the path or directly approving or approved by it.
+/// Transaction 1 is not included.
+pub fn one_hop_fraction(transactions: &Transactions, graph: &Graph, path: &[TxId]) -> f64 {
+    if transactions.is_empty() {
+        return 0.0;
+    }
+
+    let near = path
+        .iter()
+        .flat_map(|id| {
+            graph
+                .parents(*id)
+                .chain(graph.approvers(*id).into_iter().flatten().copied())
+                .chain([*id])
+        })
+        .filter(|id| transactions.contains_key(id))
+        .collect::<HashSet<_>>();
+
+    near.len() as f64 / transactions.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::transaction::Transaction;
+    use super::*;
+
+    fn sample_transactions() -> Transactions {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 1, 0));
+        transactions.insert(4, Transaction::new(3, 3, 1));
+        transactions.insert(5, Transaction::new(3, 3, 2));
+        transactions.insert(6, Transaction::new(5, 5, 3));
+        transactions.insert(7, Transaction::new(2, 2, 4));
+
+        transactions
+    }
+
+    #[test]
+    fn sample_heaviest_path() {
+        let transactions = sample_transactions();
+        let graph = Graph::new(&transactions);
+
+        let path = heaviest_path(&graph);
+
+        assert_eq!(path, vec![1, 3, 5, 6]);
+        assert_eq!(one_hop_fraction(&transactions, &graph, &path), 5.0 / 6.0);
+    }
+
+    #[test]
+    fn heaviest_path_skips_unsolid_cycle() {
+        let mut transactions = Transactions::new();
+
+        // Transactions 2 and 3 reference each other, so neither of them is solid
+        transactions.insert(2, Transaction::new(1, 3, 0));
+        transactions.insert(3, Transaction::new(2, 2, 0));
+        transactions.insert(4, Transaction::new(1, 1, 1));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(heaviest_path(&graph), vec![1, 4]);
+    }
+
+    #[test]
+    fn shortest_and_longest_paths() {
+        let transactions = sample_transactions();
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(shortest_path(&graph, 6, 1), Some(vec![6, 5, 3, 1]));
+        assert_eq!(longest_path(&graph, 6, 1), Some(vec![6, 5, 3, 1]));
+        assert_eq!(shortest_path(&graph, 4, 4), Some(vec![4]));
+        assert_eq!(longest_path(&graph, 4, 4), Some(vec![4]));
+        assert_eq!(shortest_path(&graph, 1, 6), None);
+        assert_eq!(longest_path(&graph, 7, 3), None);
+        assert_eq!(shortest_path(&graph, 9, 1), None);
+    }
+
+    #[test]
+    fn longest_path_differs_from_shortest() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(2, 2, 1));
+        transactions.insert(4, Transaction::new(3, 1, 2));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(shortest_path(&graph, 4, 1), Some(vec![4, 1]));
+        assert_eq!(longest_path(&graph, 4, 1), Some(vec![4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn no_paths_between_unsolid_transactions() {
+        let mut transactions = Transactions::new();
+
+        // Transactions 2 and 3 reference each other, so neither of them is solid
+        transactions.insert(2, Transaction::new(3, 3, 0));
+        transactions.insert(3, Transaction::new(2, 2, 0));
+        transactions.insert(4, Transaction::new(1, 1, 1));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(shortest_path(&graph, 2, 3), None);
+        assert_eq!(longest_path(&graph, 2, 3), None);
+        assert_eq!(shortest_path(&graph, 2, 2), None);
+        assert_eq!(longest_path(&graph, 4, 2), None);
+        assert_eq!(longest_path(&graph, 4, 1), Some(vec![4, 1]));
+    }
+
+    #[test]
+    fn sample_lowest_common_ancestors() {
+        let transactions = sample_transactions();
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[4, 6]),
+            Some(BTreeSet::from([3]))
+        );
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[4, 6, 7]),
+            Some(BTreeSet::from([1]))
+        );
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[6, 3]),
+            Some(BTreeSet::from([3]))
+        );
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[6]),
+            Some(BTreeSet::from([6]))
+        );
+        assert_eq!(lowest_common_ancestors(&graph, &[6, 9]), None);
+        assert_eq!(lowest_common_ancestors(&graph, &[]), None);
+    }
+
+    #[test]
+    fn multiple_lowest_common_ancestors() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 1, 0));
+        transactions.insert(4, Transaction::new(2, 3, 1));
+        transactions.insert(5, Transaction::new(2, 3, 1));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[4, 5]),
+            Some(BTreeSet::from([2, 3]))
+        );
+    }
+
+    #[test]
+    fn parent_deeper_than_approver() {
+        let mut transactions = Transactions::new();
+
+        // Transaction 4 has depth 1, its parent 3 has depth 2
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(2, 2, 1));
+        transactions.insert(4, Transaction::new(3, 1, 2));
+        transactions.insert(5, Transaction::new(4, 4, 3));
+        transactions.insert(6, Transaction::new(4, 3, 3));
+        transactions.insert(7, Transaction::new(8, 8, 3));
+
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[5, 6]),
+            Some(BTreeSet::from([4]))
+        );
+        assert_eq!(
+            lowest_common_ancestors(&graph, &[5, 6, 2]),
+            Some(BTreeSet::from([2]))
+        );
+        assert_eq!(lowest_common_ancestors(&graph, &[5, 7]), None);
+
+        assert_eq!(shortest_path(&graph, 5, 3), Some(vec![5, 4, 3]));
+        assert_eq!(shortest_path(&graph, 3, 4), None);
+    }
+
+    #[test]
+    fn empty_heaviest_path() {
+        let transactions = Transactions::new();
+        let graph = Graph::new(&transactions);
+
+        assert_eq!(heaviest_path(&graph), vec![1]);
+        assert_eq!(one_hop_fraction(&transactions, &graph, &[1]), 0.0);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::graph::{Graph, Transactions};
+use super::transaction::{Timestamp, Transaction, TxId};
+
+/// A strategy to select the parents of a new transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TipSelection {
+    /// Selects the parents uniformly at random among the visible tips.
+    Uniform,
+    /// Selects the parents by a weighted MCMC random walk towards the tips,
+    /// biased by the cumulative weight with the provided `alpha`.
+    RandomWalk { alpha: f64 },
+}
+
+impl std::fmt::Display for TipSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TipSelection::Uniform => write!(f, "uniform"),
+            TipSelection::RandomWalk { alpha } => write!(f, "walk(alpha={alpha})"),
+        }
+    }
+}
+
+/// The tangle growth simulation configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    /// The time a transaction needs to become visible to the new transactions.
+    pub delay: f64,
+    /// The parents selection strategy.
+    pub tip_selection: TipSelection,
+    /// The number of parents selected by every new transaction.
+    pub parents: usize,
+    /// How far back in time the random walks start.
+    pub walk_window: f64,
+    /// The random number generator seed.
+    pub seed: u64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            delay: 1.0,
+            tip_selection: TipSelection::Uniform,
+            parents: 2,
+            walk_window: 10.0,
+            seed: 0,
+        }
+    }
+}
+
+/// A tangle growth simulation.
+///
+/// New transactions select their parents in the graph of visible transactions,
+/// a transaction becomes visible `delay` time after it was issued.
+pub struct Simulation {
+    /// The simulation configuration.
+    config: SimulationConfig,
+    /// The random number generator used by the tip selection.
+    rng: ChaCha8Rng,
+    /// The graph of the visible transactions.
+    graph: Graph,
+    /// The list of all issued transactions.
+    transactions: Transactions,
+    /// The issue times, indexed by transaction id.
+    arrivals: Vec<f64>,
+    /// The visible cumulative weights, indexed by transaction id.
+    /// Weights are kept exact only for the transactions issued within the walk window.
+    weights: Vec<u64>,
+    /// The visible tips.
+    tips: Vec<TxId>,
+}
+
+impl Simulation {
+    /// Creates a new `Simulation` instance containing only transaction 1.
+    pub fn new(config: SimulationConfig) -> Self {
+        let rng = ChaCha8Rng::seed_from_u64(config.seed);
+
+        Self {
+            config,
+            rng,
+            graph: Graph::new(&Transactions::new()),
+            transactions: Transactions::new(),
+            arrivals: vec![f64::NEG_INFINITY, f64::NEG_INFINITY],
+            weights: vec![0, 1],
+            tips: vec![1],
+        }
+    }
+
+    /// Issues a new transaction at the provided time and returns its id.
+    /// The time must not be less than the time of the previously issued transaction.
+    pub fn issue(&mut self, time: f64) -> TxId {
+        let horizon = time - self.config.walk_window;
+
+        while self.graph.size() + 1 < self.arrivals.len()
+            && self.arrivals[self.graph.size() + 1] <= time - self.config.delay
+        {
+            self.reveal(self.graph.size() + 1, horizon);
+        }
+
+        let parents = match self.config.tip_selection {
+            TipSelection::Uniform => (0..self.config.parents)
+                .map(|_| self.random_tip())
+                .collect(),
+            TipSelection::RandomWalk { alpha } => {
+                let start = self.walk_start(horizon);
+
+                (0..self.config.parents)
+                    .map(|_| self.random_walk(start, alpha))
+                    .collect()
+            }
+        };
+
+        let id = self.arrivals.len();
+
+        self.arrivals.push(time);
+        self.weights.push(0);
+        self.transactions
+            .insert(id, Transaction::with_parents(parents, time as Timestamp));
+
+        id
+    }
+
+    /// Returns the list of all issued transactions.
+    pub fn transactions(&self) -> &Transactions {
+        &self.transactions
+    }
+
+    /// Returns the list of all issued transactions consuming the simulation.
+    pub fn into_transactions(self) -> Transactions {
+        self.transactions
+    }
+
+    /// Makes the transaction visible.
+    fn reveal(&mut self, id: TxId, horizon: f64) {
+        let tx = &self.transactions[&id];
+
+        self.graph.insert(id, tx);
+
+        self.tips.retain(|tip| !tx.parents.contains(tip));
+        self.tips.push(id);
+
+        let mut visited = HashSet::from([id]);
+        let mut stack = vec![id];
+
+        while let Some(i) = stack.pop() {
+            self.weights[i] += 1;
+
+            for parent in self.graph.parents(i) {
+                if self.arrivals[parent] >= horizon && visited.insert(parent) {
+                    stack.push(parent);
+                }
+            }
+        }
+    }
+
+    /// Returns a random visible tip.
+    fn random_tip(&mut self) -> TxId {
+        self.tips[self.rng.gen_range(0..self.tips.len())]
+    }
+
+    /// Returns the latest visible transaction issued before the provided time.
+    fn walk_start(&self, time: f64) -> TxId {
+        let visible = &self.arrivals[1..=self.graph.size()];
+
+        visible.partition_point(|arrival| *arrival <= time).max(1)
+    }
+
+    /// Walks from the provided transaction to a tip and returns the tip.
+    fn random_walk(&mut self, start: TxId, alpha: f64) -> TxId {
+        let mut current = start;
+
+        while let Some(approvers) = self.graph.approvers(current) {
+            let max_weight = approvers
+                .iter()
+                .map(|a| self.weights[*a])
+                .max()
+                .expect("the approvers list can not be empty");
+
+            let probabilities = approvers
+                .iter()
+                .map(|a| (-alpha * (max_weight - self.weights[*a]) as f64).exp())
+                .collect::<Vec<_>>();
+
+            let mut choice = self.rng.gen::<f64>() * probabilities.iter().sum::<f64>();
+
+            current = *approvers
+                .last()
+                .expect("the approvers list can not be empty");
+
+            for (approver, probability) in approvers.iter().zip(probabilities) {
+                if choice < probability {
+                    current = *approver;
+                    break;
+                }
+
+                choice -= probability;
+            }
+        }
+
+        current
+    }
+}
+
+/// Regrows the provided transactions list with the same timestamps
+/// but the parents selected by the simulation.
+pub fn replay_txs(transactions: &Transactions, config: &SimulationConfig) -> Transactions {
+    let mut simulation = Simulation::new(config.clone());
+
+    transactions
+        .iter()
+        .sorted_by_key(|(id, tx)| (tx.timestamp, **id))
+        .for_each(|(_, tx)| {
+            simulation.issue(tx.timestamp as f64);
+        });
+
+    simulation.into_transactions()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_transactions_see_only_visible_ones() {
+        let mut simulation = Simulation::new(SimulationConfig::default());
+
+        assert_eq!(simulation.issue(0.0), 2);
+        assert_eq!(simulation.issue(0.5), 3);
+        assert_eq!(simulation.issue(1.0), 4);
+        assert_eq!(simulation.issue(1.6), 5);
+
+        let transactions = simulation.transactions();
+
+        assert_eq!(transactions[&2], Transaction::new(1, 1, 0));
+        assert_eq!(transactions[&3], Transaction::new(1, 1, 0));
+        assert_eq!(transactions[&4], Transaction::new(2, 2, 1));
+        assert!(transactions[&5].parents.iter().all(|p| [2, 3].contains(p)));
+    }
+
+    #[test]
+    fn cumulative_weights() {
+        let mut simulation = Simulation::new(SimulationConfig::default());
+
+        for time in [0.0, 2.0, 4.0, 6.0] {
+            simulation.issue(time);
+        }
+
+        assert_eq!(simulation.graph.size(), 4);
+        assert_eq!(simulation.weights[1..5], [1, 3, 2, 1]);
+        assert_eq!(simulation.walk_start(1.0), 2);
+        assert_eq!(simulation.tips, vec![4]);
+    }
+
+    #[test]
+    fn issue_with_more_parents() {
+        let mut simulation = Simulation::new(SimulationConfig {
+            parents: 5,
+            ..Default::default()
+        });
+
+        for time in [0.0, 0.0, 0.0, 2.0] {
+            simulation.issue(time);
+        }
+
+        let transactions = simulation.transactions();
+
+        assert_eq!(transactions[&2].parents, vec![1; 5]);
+        assert_eq!(transactions[&5].parents.len(), 5);
+        assert!(transactions[&5]
+            .parents
+            .iter()
+            .all(|p| [2, 3, 4].contains(p)));
+    }
+
+    #[test]
+    fn replay_keeps_timestamps() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 0));
+        transactions.insert(4, Transaction::new(2, 2, 1));
+        transactions.insert(5, Transaction::new(3, 3, 2));
+        transactions.insert(6, Transaction::new(3, 4, 3));
+
+        let config = SimulationConfig {
+            tip_selection: TipSelection::RandomWalk { alpha: 0.5 },
+            ..Default::default()
+        };
+
+        let replayed = replay_txs(&transactions, &config);
+
+        assert_eq!(replayed.len(), transactions.len());
+        assert_eq!(replayed, replay_txs(&transactions, &config));
+
+        for (id, tx) in &replayed {
+            assert_eq!(tx.timestamp, transactions[id].timestamp);
+            assert!(tx.parents.iter().all(|p| p < id));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use super::distribution::Distribution;
+use super::gaps::ParentGapStats;
+use super::graph::Depth;
+use super::transaction::{Timestamp, Transaction};
+
+/// A snapshot of the ledger statistics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    /// The number of transactions(transaction 1 is not included).
+    pub transactions: usize,
+    /// The number of solid transactions(transaction 1 is not included).
+    pub solid_transactions: usize,
+    /// The number of transactions with incomplete past cones.
+    pub unsolid_transactions: usize,
+    /// The number of transactions referencing parents which are not in the ledger.
+    pub orphan_transactions: usize,
+    /// The number of transactions without approvers.
+    pub tips: usize,
+    /// The average depth of the directed acyclic graph.
+    pub avg_dag_depth: f32,
+    /// The average number of transactions per depth.
+    pub avg_txs_per_depth: f32,
+    /// The average number of in-references per node.
+    pub avg_ref: f32,
+    /// The average number of transactions per timestamp.
+    pub avg_txs_per_ts: f32,
+    /// The distributions of the gaps to the parents.
+    pub parent_gaps: ParentGapStats,
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "AVG DAG DEPTH: {}", self.avg_dag_depth)?;
+        writeln!(f, "AVG TXS PER DEPTH: {}", self.avg_txs_per_depth)?;
+        writeln!(f, "AVG REF: {}", self.avg_ref)?;
+        writeln!(f, "---")?;
+        writeln!(f, "AVG TXS PER TS: {}", self.avg_txs_per_ts)?;
+        writeln!(f, "---")?;
+        writeln!(f, "AVG ID GAP: {}", self.parent_gaps.id.mean)?;
+        writeln!(f, "MAX ID GAP: {}", self.parent_gaps.id.max)?;
+        writeln!(f, "AVG TS GAP: {}", self.parent_gaps.timestamp.mean)?;
+        writeln!(f, "MAX TS GAP: {}", self.parent_gaps.timestamp.max)?;
+        writeln!(f, "AVG DEPTH GAP: {}", self.parent_gaps.depth.mean)?;
+        writeln!(f, "MAX DEPTH GAP: {}", self.parent_gaps.depth.max)?;
+        writeln!(f, "---")?;
+        writeln!(f, "TXS: {}", self.transactions)?;
+        writeln!(f, "SOLID TXS: {}", self.solid_transactions)?;
+        writeln!(f, "UNSOLID TXS: {}", self.unsolid_transactions)?;
+        writeln!(f, "ORPHAN TXS: {}", self.orphan_transactions)?;
+        write!(f, "TIPS: {}", self.tips)
+    }
+}
+
+/// The statistics updated on every new transaction.
+#[derive(Debug, Default)]
+pub(super) struct RunningStats {
+    /// The sum of the solid transactions depths.
+    pub depths_sum: Depth,
+    /// The maximum depth.
+    pub max_depth: Depth,
+    /// The number of transactions per timestamp.
+    pub timestamps: HashMap<Timestamp, usize>,
+    /// The number of parent references per id gap.
+    pub id_gaps: BTreeMap<i64, usize>,
+    /// The number of parent references per timestamp gap.
+    pub timestamp_gaps: BTreeMap<i64, usize>,
+    /// The number of parent references per depth gap.
+    pub depth_gaps: BTreeMap<i64, usize>,
+}
+
+impl RunningStats {
+    /// Accounts the new transaction.
+    pub fn add_tx(&mut self, tx: &Transaction) {
+        *self.timestamps.entry(tx.timestamp).or_default() += 1;
+    }
+
+    /// Accounts the new solid transaction depth.
+    pub fn add_depth(&mut self, depth: Depth) {
+        self.depths_sum += depth;
+        self.max_depth = self.max_depth.max(depth);
+    }
+
+    /// Accounts the id gap of a new parent reference.
+    pub fn add_id_gap(&mut self, gap: i64) {
+        *self.id_gaps.entry(gap).or_default() += 1;
+    }
+
+    /// Accounts the timestamp gap of a parent reference whose both transactions are known.
+    pub fn add_timestamp_gap(&mut self, gap: i64) {
+        *self.timestamp_gaps.entry(gap).or_default() += 1;
+    }
+
+    /// Accounts the depth gap of a parent reference whose both transactions are solid.
+    pub fn add_depth_gap(&mut self, gap: i64) {
+        *self.depth_gaps.entry(gap).or_default() += 1;
+    }
+
+    /// Returns the distributions of the accounted parent gaps.
+    pub fn parent_gaps(&self) -> ParentGapStats {
+        ParentGapStats {
+            id: Distribution::from_counts(&self.id_gaps),
+            timestamp: Distribution::from_counts(&self.timestamp_gaps),
+            depth: Distribution::from_counts(&self.depth_gaps),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::transaction::Transaction;
+use super::{LedgerError, Result};
+
+/// An incremental parser of a growing database.
+///
+/// The database is provided in chunks of any size, transactions are parsed
+/// as soon as their lines are complete. The transactions number line is skipped,
+/// since it is outdated as soon as new transactions are appended.
+#[derive(Debug, Default)]
+pub struct TxStream {
+    /// The not yet terminated line.
+    pending: Vec<u8>,
+    /// Whether the transactions number line is already skipped.
+    header_skipped: bool,
+}
+
+impl TxStream {
+    /// Creates a new `TxStream` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the complete lines of the provided chunk and returns their transactions.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Transaction>> {
+        self.pending.extend_from_slice(chunk);
+
+        let Some(end) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return Ok(Vec::new());
+        };
+
+        let lines = self.pending.drain(..=end).collect::<Vec<_>>();
+
+        String::from_utf8_lossy(&lines)
+            .lines()
+            .filter_map(|l| self.parse_line(l))
+            .collect()
+    }
+
+    /// Parses the not yet terminated line as the last one.
+    pub fn finish(&mut self) -> Result<Option<Transaction>> {
+        let line = std::mem::take(&mut self.pending);
+
+        self.parse_line(&String::from_utf8_lossy(&line)).transpose()
+    }
+
+    /// Parses the line, the transactions number line and empty lines are skipped.
+    fn parse_line(&mut self, line: &str) -> Option<Result<Transaction>> {
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        if !self.header_skipped {
+            self.header_skipped = true;
+
+            return line
+                .trim()
+                .parse::<usize>()
+                .err()
+                .map(|e| Err(LedgerError::ParseIntError(e)));
+        }
+
+        Some(
+            line.parse::<Transaction>()
+                .map_err(LedgerError::ParseTxError),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_database() {
+        let mut stream = TxStream::new();
+
+        assert_eq!(stream.push(b"2\n1 1").unwrap(), vec![]);
+        assert_eq!(
+            stream.push(b" 0\n1 2 0\n\n2 ").unwrap(),
+            vec![Transaction::new(1, 1, 0), Transaction::new(1, 2, 0)]
+        );
+        assert_eq!(stream.push(b"2 1").unwrap(), vec![]);
+        assert_eq!(stream.finish().unwrap(), Some(Transaction::new(2, 2, 1)));
+        assert_eq!(stream.finish().unwrap(), None);
+    }
+
+    #[test]
+    fn broken_database() {
+        let mut stream = TxStream::new();
+
+        assert!(matches!(
+            stream.push(b"\xF0\x9F\xA6\x80\n").unwrap_err(),
+            LedgerError::ParseIntError(_)
+        ));
+        assert!(matches!(
+            stream.push(b"1 1 0\n1\n").unwrap_err(),
+            LedgerError::ParseTxError(_)
+        ));
+    }
+}
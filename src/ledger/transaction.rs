@@ -1,20 +1,28 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::value::Transfer;
+
 /// Type alias for timestamp.
 pub type Timestamp = u64;
 /// Type alias for transaction id.
 pub type TxId = usize;
 
+/// The maximum number of parents of a transaction.
+pub const MAX_PARENTS: usize = 8;
+
 /// A transaction in-memory representation.
 /// Can be parsed from the provided transactions database file.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Transaction {
-    /// The transaction's left parent.
-    pub left: TxId,
-    /// The transaction's right parent.
-    pub right: TxId,
+    /// The transaction's parents, from 1 to `MAX_PARENTS`.
+    pub parents: Vec<TxId>,
     /// The transaction's timestamp.
     pub timestamp: Timestamp,
+    /// The values spent from the addresses.
+    pub inputs: Vec<Transfer>,
+    /// The values sent to the addresses.
+    pub outputs: Vec<Transfer>,
 }
 
 /// Contains all possible transaction deserialization errors.
@@ -22,40 +30,172 @@ pub struct Transaction {
 pub enum ParseTxError {
     #[error("parse int error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
-    #[error("wrong fields number: expected '{0}', actual '{1}")]
-    WrongFieldsNumberError(usize, usize),
+    #[error("wrong parents number: expected 1 to {MAX_PARENTS}, actual '{0}'")]
+    WrongParentsNumberError(usize),
+    #[error("invalid transfer: '{0}'")]
+    InvalidTransferError(String),
 }
 
 impl std::str::FromStr for Transaction {
     type Err = ParseTxError;
 
+    /// Parses the `parent... timestamp [-address:amount]... [+address:amount]...` fields,
+    /// e.g. `left right timestamp`. Inputs are prefixed with `-`, outputs with `+`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const EXPECTED_FIELDS_NUMBER: usize = 3;
+        let mut fields = s.split_whitespace().collect::<Vec<_>>();
+
+        let transfers_start = fields
+            .iter()
+            .rposition(|f| !f.starts_with(['-', '+']))
+            .map_or(0, |i| i + 1);
+        let transfers = fields.split_off(transfers_start);
+
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+
+        for transfer in transfers {
+            match transfer.split_at(1) {
+                ("-", input) => inputs.push(input.parse()?),
+                (_, output) => outputs.push(output.parse()?),
+            }
+        }
+
+        let Some((timestamp, parents)) = fields.split_last() else {
+            return Err(ParseTxError::WrongParentsNumberError(0));
+        };
+
+        if !is_valid_parents_number(parents.len()) {
+            return Err(ParseTxError::WrongParentsNumberError(parents.len()));
+        }
+
+        let tx = Transaction::with_parents(
+            parents
+                .iter()
+                .map(|p| p.parse::<TxId>())
+                .collect::<Result<_, _>>()?,
+            timestamp.parse::<Timestamp>()?,
+        );
+
+        Ok(tx.with_transfers(inputs, outputs))
+    }
+}
 
-        let fields = s.split_whitespace().collect::<Vec<_>>();
-        let fields_number = fields.len();
+impl std::fmt::Display for Transaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for parent in &self.parents {
+            write!(f, "{parent} ")?;
+        }
+
+        write!(f, "{}", self.timestamp)?;
+
+        for input in &self.inputs {
+            write!(f, " -{input}")?;
+        }
 
-        if EXPECTED_FIELDS_NUMBER != fields_number {
-            return Err(ParseTxError::WrongFieldsNumberError(
-                EXPECTED_FIELDS_NUMBER,
-                fields_number,
-            ));
+        for output in &self.outputs {
+            write!(f, " +{output}")?;
         }
 
-        Ok(Transaction::new(
-            fields[0].parse::<TxId>()?,
-            fields[1].parse::<TxId>()?,
-            fields[2].parse::<Timestamp>()?,
-        ))
+        Ok(())
     }
 }
 
 impl Transaction {
+    /// Creates a new `Transaction` instance with the left and the right parents.
     pub fn new(left: TxId, right: TxId, timestamp: Timestamp) -> Self {
+        Self::with_parents(vec![left, right], timestamp)
+    }
+
+    /// Creates a new `Transaction` instance with the provided parents.
+    pub fn with_parents(parents: Vec<TxId>, timestamp: Timestamp) -> Self {
         Self {
-            left,
-            right,
+            parents,
             timestamp,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Returns the transaction moving the values from the inputs to the outputs.
+    pub fn with_transfers(mut self, inputs: Vec<Transfer>, outputs: Vec<Transfer>) -> Self {
+        self.inputs = inputs;
+        self.outputs = outputs;
+        self
+    }
+
+    /// Returns true if the transaction moves any value.
+    pub fn has_transfers(&self) -> bool {
+        !self.inputs.is_empty() || !self.outputs.is_empty()
+    }
+}
+
+/// Returns true if a transaction can have the provided number of parents.
+pub fn is_valid_parents_number(parents_number: usize) -> bool {
+    (1..=MAX_PARENTS).contains(&parents_number)
+}
+
+/// A transaction record with an explicit id, used by the JSON Lines and binary formats.
+/// The legacy `left` and `right` fields are read as the first parents.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "RawTxRecord")]
+pub(super) struct TxRecord {
+    /// The transaction id.
+    pub id: TxId,
+    /// The transaction's parents.
+    pub parents: Vec<TxId>,
+    /// The transaction's timestamp.
+    pub timestamp: Timestamp,
+    /// The values spent from the addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<Transfer>,
+    /// The values sent to the addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<Transfer>,
+}
+
+impl TxRecord {
+    /// Creates a new `TxRecord` instance of the transaction.
+    pub fn new(id: TxId, tx: &Transaction) -> Self {
+        Self {
+            id,
+            parents: tx.parents.clone(),
+            timestamp: tx.timestamp,
+            inputs: tx.inputs.clone(),
+            outputs: tx.outputs.clone(),
+        }
+    }
+}
+
+/// A transaction record with either the parents list or the legacy `left` and `right` fields.
+#[derive(Deserialize)]
+struct RawTxRecord {
+    id: TxId,
+    #[serde(default)]
+    left: Option<TxId>,
+    #[serde(default)]
+    right: Option<TxId>,
+    #[serde(default)]
+    parents: Vec<TxId>,
+    timestamp: Timestamp,
+    #[serde(default)]
+    inputs: Vec<Transfer>,
+    #[serde(default)]
+    outputs: Vec<Transfer>,
+}
+
+impl From<RawTxRecord> for TxRecord {
+    fn from(raw: RawTxRecord) -> Self {
+        Self {
+            id: raw.id,
+            parents: raw
+                .left
+                .into_iter()
+                .chain(raw.right)
+                .chain(raw.parents)
+                .collect(),
+            timestamp: raw.timestamp,
+            inputs: raw.inputs,
+            outputs: raw.outputs,
         }
     }
 }
@@ -71,29 +211,64 @@ mod tests {
         assert_eq!(
             Transaction::from_str("1 2 3").unwrap(),
             Transaction {
-                left: 1,
-                right: 2,
-                timestamp: 3
+                parents: vec![1, 2],
+                timestamp: 3,
+                inputs: vec![],
+                outputs: vec![],
             }
         );
+        assert_eq!(
+            Transaction::from_str("7 3").unwrap(),
+            Transaction::with_parents(vec![7], 3)
+        );
+        assert_eq!(
+            Transaction::from_str("1 2 3 4 5 6 7 8 9").unwrap(),
+            Transaction::with_parents(vec![1, 2, 3, 4, 5, 6, 7, 8], 9)
+        );
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let tx = Transaction::new(3, 4, 5);
+
+        assert_eq!(tx.to_string(), "3 4 5");
+        assert_eq!(Transaction::from_str(&tx.to_string()).unwrap(), tx);
+
+        let tx = Transaction::with_parents(vec![3, 4, 2], 5);
+
+        assert_eq!(tx.to_string(), "3 4 2 5");
+        assert_eq!(Transaction::from_str(&tx.to_string()).unwrap(), tx);
     }
 
     #[test]
     fn fields_number_less_then_expected() {
         assert_eq!(
-            Transaction::from_str("1 2").unwrap_err(),
-            ParseTxError::WrongFieldsNumberError(3, 2)
+            Transaction::from_str("1").unwrap_err(),
+            ParseTxError::WrongParentsNumberError(0)
+        );
+        assert_eq!(
+            Transaction::from_str("").unwrap_err(),
+            ParseTxError::WrongParentsNumberError(0)
         );
     }
 
     #[test]
     fn fields_number_more_then_expected() {
         assert_eq!(
-            Transaction::from_str("1 2 3 4").unwrap_err(),
-            ParseTxError::WrongFieldsNumberError(3, 4)
+            Transaction::from_str("1 2 3 4 5 6 7 8 9 10").unwrap_err(),
+            ParseTxError::WrongParentsNumberError(9)
         );
     }
 
+    #[test]
+    fn legacy_record_fields() {
+        let record =
+            serde_json::from_str::<TxRecord>(r#"{"id":4,"left":2,"right":3,"timestamp":1}"#)
+                .unwrap();
+
+        assert_eq!(record.parents, vec![2, 3]);
+    }
+
     #[test]
     fn wrong_left_parent_field() {
         assert!(matches!(
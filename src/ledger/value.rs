@@ -0,0 +1,490 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::distribution::Distribution;
+use super::graph::{Graph, Transactions};
+use super::transaction::{ParseTxError, Transaction, TxId};
+
+/// Type alias for address.
+pub type Address = String;
+/// Type alias for amount.
+pub type Amount = u64;
+
+/// A value moved from or to an address by a transaction.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Transfer {
+    /// The address.
+    pub address: Address,
+    /// The moved amount.
+    pub amount: Amount,
+}
+
+impl std::str::FromStr for Transfer {
+    type Err = ParseTxError;
+
+    /// Parses the `address:amount` field.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, amount) = s
+            .rsplit_once(':')
+            .filter(|(address, _)| !address.is_empty())
+            .ok_or_else(|| ParseTxError::InvalidTransferError(s.to_owned()))?;
+
+        Ok(Transfer::new(address, amount.parse::<Amount>()?))
+    }
+}
+
+impl std::fmt::Display for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.address, self.amount)
+    }
+}
+
+impl Transfer {
+    /// Creates a new `Transfer` instance.
+    pub fn new(address: &str, amount: Amount) -> Self {
+        Self {
+            address: address.to_owned(),
+            amount,
+        }
+    }
+}
+
+/// The address balances after applying the value transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Balances {
+    /// The balances by address, addresses with zero balance included.
+    pub balances: BTreeMap<Address, Amount>,
+    /// The sum of all the balances.
+    pub total_supply: Amount,
+    /// The number of addresses with a positive balance.
+    pub active_addresses: usize,
+    /// The distribution of the positive balances.
+    pub distribution: Distribution,
+    /// The value transactions which were applied.
+    pub applied: usize,
+    /// The value transactions which were rejected: unbalanced, spending missing funds, overflowing
+    /// a balance or the total supply, or issuing values without approving only transaction 1.
+    pub rejected: BTreeSet<TxId>,
+}
+
+/// Applies the value transactions in topological order and returns the address balances.
+///
+/// Only the issuance transactions, approving transaction 1 alone, may have outputs without inputs,
+/// any other transaction without inputs would inflate the supply and is rejected. Transactions with inputs
+/// must spend funds available in their own past cones and their inputs must sum to their outputs;
+/// of two concurrent transactions spending the same funds the later one in the order finds them missing.
+/// Unsolid transactions are not applied.
+pub fn calculate_balances(transactions: &Transactions, graph: &Graph) -> Balances {
+    let PastFunds {
+        valid,
+        invalid: mut rejected,
+        ..
+    } = check_past_funds(transactions, graph);
+
+    let mut balances = BTreeMap::<Address, Amount>::new();
+    let mut total_supply: Amount = 0;
+    let mut applied = 0;
+
+    for id in valid {
+        let tx = &transactions[&id];
+
+        // Only the issuance transactions change the total supply, the transfers move it
+        let supply = if tx.inputs.is_empty() {
+            sum(&tx.outputs).and_then(|issued| total_supply.checked_add(issued))
+        } else {
+            Some(total_supply)
+        };
+
+        match supply.zip(apply(&balances, &tx.inputs, &tx.outputs)) {
+            Some((supply, updated)) => {
+                balances.extend(updated);
+                total_supply = supply;
+                applied += 1;
+            }
+            None => {
+                rejected.insert(id);
+            }
+        }
+    }
+
+    let positive = balances
+        .values()
+        .filter(|b| **b > 0)
+        .copied()
+        .collect::<Vec<_>>();
+
+    Balances {
+        total_supply,
+        active_addresses: positive.len(),
+        distribution: Distribution::new(positive.iter().map(|b| *b as f64)),
+        balances,
+        applied,
+        rejected,
+    }
+}
+
+/// The value transactions checked against the funds in their own past cones.
+#[derive(Debug, Default)]
+pub(super) struct PastFunds {
+    /// The valid value transactions in topological order.
+    pub valid: Vec<TxId>,
+    /// The invalid value transactions: unbalanced, overflowing, spending funds missing in their past cones,
+    /// or issuing values without approving only transaction 1.
+    pub invalid: BTreeSet<TxId>,
+    /// The balance changes of each address by the valid transactions, in topological order.
+    pub changes: BTreeMap<Address, Vec<Change>>,
+    /// The valid transactions spending from each address, in topological order.
+    pub spends: BTreeMap<Address, Vec<Spend>>,
+}
+
+/// A balance change of an address by a valid transaction.
+#[derive(Debug)]
+pub(super) struct Change {
+    /// The position of the transaction among the valid transactions.
+    position: usize,
+    /// The outputs minus the inputs of the address.
+    pub amount: i128,
+}
+
+/// A valid transaction spending from an address.
+#[derive(Debug)]
+pub(super) struct Spend {
+    /// The transaction id.
+    pub id: TxId,
+    /// The amount spent from the address.
+    pub amount: Amount,
+    /// The index of the spend in the changes of the address.
+    index: usize,
+    /// The indexes of the changes of the address in the past cone of the spend.
+    past: Vec<u64>,
+}
+
+impl Spend {
+    /// Checks if the other spend of the same address is in the past cone of this one.
+    pub fn approves(&self, other: &Spend) -> bool {
+        contains(&self.past, other.index)
+    }
+
+    /// Returns the balance of the address in the shared past of the spends.
+    pub fn shared_balance(&self, other: &Spend, changes: &[Change]) -> i128 {
+        let mut balance = 0;
+
+        for (i, (a, b)) in self.past.iter().zip(&other.past).enumerate() {
+            let mut word = a & b;
+
+            while word != 0 {
+                balance += changes[i * 64 + word.trailing_zeros() as usize].amount;
+                word &= word - 1;
+            }
+        }
+
+        balance
+    }
+}
+
+/// Checks the value transactions in one topological pass, a transaction is valid if it issues values
+/// approving transaction 1 alone or spends funds of the valid transactions in its past cone.
+pub(super) fn check_past_funds(transactions: &Transactions, graph: &Graph) -> PastFunds {
+    let mut funds = PastFunds::default();
+    let mut pasts = HashMap::<TxId, Vec<u64>>::new();
+    let mut pending = HashMap::new();
+
+    // The past cones are merged over the positions of the valid transactions only, the cone
+    // of a transaction is dropped as soon as all its approvers have merged it
+    for id in graph.topological_order() {
+        let mut past = Vec::new();
+
+        for parent in graph.parents(id) {
+            union(&mut past, &pasts[&parent]);
+
+            let counter = pending.get_mut(&parent).unwrap();
+            *counter -= 1;
+
+            if *counter == 0 {
+                pending.remove(&parent);
+                pasts.remove(&parent);
+            }
+        }
+
+        if let Some(tx) = transactions
+            .get(&id)
+            .filter(|tx| !tx.inputs.is_empty() || !tx.outputs.is_empty())
+        {
+            if funds.check(id, tx, &past) {
+                insert(&mut past, funds.valid.len() - 1);
+            } else {
+                funds.invalid.insert(id);
+            }
+        }
+
+        let approvers = graph
+            .approvers(id)
+            .into_iter()
+            .flatten()
+            .filter(|approver| graph.is_solid(**approver))
+            .count();
+
+        if approvers > 0 {
+            pending.insert(id, approvers);
+            pasts.insert(id, past);
+        }
+    }
+
+    funds
+}
+
+impl PastFunds {
+    /// Checks the transaction against the funds in its past cone and records it if it is valid.
+    fn check(&mut self, id: TxId, tx: &Transaction, past: &[u64]) -> bool {
+        if tx.inputs.is_empty() {
+            if !is_issuance(tx) || sum(&tx.outputs).is_none() {
+                return false;
+            }
+        } else if sum(&tx.inputs).is_none() || sum(&tx.inputs) != sum(&tx.outputs) {
+            return false;
+        }
+
+        let mut spent = BTreeMap::<&Address, Amount>::new();
+
+        for input in &tx.inputs {
+            *spent.entry(&input.address).or_default() += input.amount;
+        }
+
+        let mut spends = BTreeMap::new();
+
+        for (address, amount) in spent {
+            let changes = self.changes.get(address).map_or(&[][..], Vec::as_slice);
+            let mut indexes = Vec::new();
+            let mut balance = 0;
+
+            for (index, change) in changes.iter().enumerate() {
+                if contains(past, change.position) {
+                    insert(&mut indexes, index);
+                    balance += change.amount;
+                }
+            }
+
+            if i128::from(amount) > balance {
+                return false;
+            }
+
+            spends.insert(address, (amount, indexes));
+        }
+
+        let mut amounts = BTreeMap::<&Address, i128>::new();
+
+        for output in &tx.outputs {
+            *amounts.entry(&output.address).or_default() += i128::from(output.amount);
+        }
+
+        for input in &tx.inputs {
+            *amounts.entry(&input.address).or_default() -= i128::from(input.amount);
+        }
+
+        let position = self.valid.len();
+
+        for (address, amount) in amounts {
+            let changes = self.changes.entry(address.clone()).or_default();
+
+            if let Some((spent, past)) = spends.remove(address) {
+                self.spends.entry(address.clone()).or_default().push(Spend {
+                    id,
+                    amount: spent,
+                    index: changes.len(),
+                    past,
+                });
+            }
+
+            changes.push(Change { position, amount });
+        }
+
+        self.valid.push(id);
+
+        true
+    }
+}
+
+/// Checks if the bit set contains the index.
+fn contains(bits: &[u64], index: usize) -> bool {
+    bits.get(index / 64)
+        .is_some_and(|word| word & (1 << (index % 64)) != 0)
+}
+
+/// Adds the index to the bit set.
+fn insert(bits: &mut Vec<u64>, index: usize) {
+    if bits.len() <= index / 64 {
+        bits.resize(index / 64 + 1, 0);
+    }
+
+    bits[index / 64] |= 1 << (index % 64);
+}
+
+/// Adds the indexes of the other bit set.
+fn union(bits: &mut Vec<u64>, other: &[u64]) {
+    if bits.len() < other.len() {
+        bits.resize(other.len(), 0);
+    }
+
+    for (word, other) in bits.iter_mut().zip(other) {
+        *word |= other;
+    }
+}
+
+/// Checks if the transaction approves transaction 1 alone, so it may issue values.
+fn is_issuance(tx: &Transaction) -> bool {
+    tx.parents.iter().all(|parent| *parent == 1)
+}
+
+/// Returns the sum of the transfers amounts, or `None` if it overflows.
+fn sum(transfers: &[Transfer]) -> Option<Amount> {
+    transfers
+        .iter()
+        .try_fold(0 as Amount, |sum, t| sum.checked_add(t.amount))
+}
+
+/// Returns the updated balances of the transfers addresses, or `None` if the transfers are invalid.
+fn apply(
+    balances: &BTreeMap<Address, Amount>,
+    inputs: &[Transfer],
+    outputs: &[Transfer],
+) -> Option<BTreeMap<Address, Amount>> {
+    if !inputs.is_empty() && sum(inputs)? != sum(outputs)? {
+        return None;
+    }
+
+    let mut updated = BTreeMap::new();
+
+    for input in inputs {
+        let balance = updated
+            .get(&input.address)
+            .or_else(|| balances.get(&input.address))
+            .copied()
+            .unwrap_or_default();
+
+        updated.insert(input.address.clone(), balance.checked_sub(input.amount)?);
+    }
+
+    for output in outputs {
+        let balance = updated
+            .get(&output.address)
+            .or_else(|| balances.get(&output.address))
+            .copied()
+            .unwrap_or_default();
+
+        updated.insert(output.address.clone(), balance.checked_add(output.amount)?);
+    }
+
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_transfer() {
+        assert_eq!("ADDR:5".parse::<Transfer>(), Ok(Transfer::new("ADDR", 5)));
+        assert_eq!(Transfer::new("A:B", 7).to_string(), "A:B:7");
+        assert_eq!("A:B:7".parse::<Transfer>(), Ok(Transfer::new("A:B", 7)));
+        assert_eq!(
+            ":5".parse::<Transfer>(),
+            Err(ParseTxError::InvalidTransferError(":5".to_owned()))
+        );
+        assert!(matches!(
+            "ADDR:-5".parse::<Transfer>(),
+            Err(ParseTxError::ParseIntError(_))
+        ));
+    }
+
+    #[test]
+    fn apply_in_topological_order() {
+        let mut transactions = Transactions::new();
+
+        // Issues 10 to A
+        transactions.insert(
+            2,
+            Transaction::new(1, 1, 0).with_transfers(vec![], vec![Transfer::new("A", 10)]),
+        );
+        // Moves 4 from A to B, approving the issuance
+        transactions.insert(
+            3,
+            Transaction::new(2, 2, 1).with_transfers(
+                vec![Transfer::new("A", 10)],
+                vec![Transfer::new("A", 6), Transfer::new("B", 4)],
+            ),
+        );
+        // Spends more than B has
+        transactions.insert(
+            4,
+            Transaction::new(3, 3, 2)
+                .with_transfers(vec![Transfer::new("B", 5)], vec![Transfer::new("C", 5)]),
+        );
+        // Unbalanced
+        transactions.insert(
+            5,
+            Transaction::new(3, 3, 2)
+                .with_transfers(vec![Transfer::new("B", 4)], vec![Transfer::new("C", 3)]),
+        );
+        // Moves all the funds of B to C
+        transactions.insert(
+            6,
+            Transaction::new(3, 1, 2)
+                .with_transfers(vec![Transfer::new("B", 4)], vec![Transfer::new("C", 4)]),
+        );
+        transactions.insert(7, Transaction::new(6, 6, 3));
+
+        let balances = calculate_balances(&transactions, &Graph::new(&transactions));
+
+        assert_eq!(
+            balances.balances,
+            BTreeMap::from([
+                ("A".to_owned(), 6),
+                ("B".to_owned(), 0),
+                ("C".to_owned(), 4)
+            ])
+        );
+        assert_eq!(balances.total_supply, 10);
+        assert_eq!(balances.active_addresses, 2);
+        assert_eq!(balances.distribution.max, 6.0);
+        assert_eq!(balances.applied, 3);
+        assert_eq!(balances.rejected, BTreeSet::from([4, 5]));
+    }
+
+    #[test]
+    fn issue_only_approving_transaction_1() {
+        let mut transactions = Transactions::new();
+
+        // Issues 10 to A
+        transactions.insert(
+            2,
+            Transaction::with_parents(vec![1], 0)
+                .with_transfers(vec![], vec![Transfer::new("A", 10)]),
+        );
+        // Issues 5 to B
+        transactions.insert(
+            3,
+            Transaction::new(1, 1, 0).with_transfers(vec![], vec![Transfer::new("B", 5)]),
+        );
+        // Mints 7 to A without inputs
+        transactions.insert(
+            4,
+            Transaction::new(2, 3, 1).with_transfers(vec![], vec![Transfer::new("A", 7)]),
+        );
+        // Mints 3 to C without inputs, approving transaction 1 too
+        transactions.insert(
+            5,
+            Transaction::new(1, 2, 1).with_transfers(vec![], vec![Transfer::new("C", 3)]),
+        );
+
+        let balances = calculate_balances(&transactions, &Graph::new(&transactions));
+
+        assert_eq!(
+            balances.balances,
+            BTreeMap::from([("A".to_owned(), 10), ("B".to_owned(), 5)])
+        );
+        assert_eq!(balances.total_supply, 15);
+        assert_eq!(balances.applied, 2);
+        assert_eq!(balances.rejected, BTreeSet::from([4, 5]));
+    }
+}
@@ -7,3 +7,5 @@
 #![deny(unsafe_code)]
 
 pub mod ledger;
+pub mod metrics;
+pub mod server;
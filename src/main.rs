@@ -9,12 +9,9 @@
 #[macro_use]
 extern crate clap;
 
-use anyhow::Result;
-
-use ledgerstats::ledger::{self, Ledger};
+mod commands;
 
-/// A file path with a sample list of transactions.
-const DEFAULT_DB_FILE_PATH: &str = "./database.txt";
+use anyhow::Result;
 
 fn main() -> Result<()> {
     let matches = clap_app!(ledgerstats =>
@@ -22,34 +19,143 @@ fn main() -> Result<()> {
         (author: env!("CARGO_PKG_AUTHORS"))
         (about: "IOTA ledgerstats application parses a given transactions list in memory and returns relevant statistics.")
         (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+        (@arg INPUT_FORMAT: --("input-format") +takes_value +global possible_value[text jsonl csv binary hashed] default_value("text") "format of the list of transactions")
+        (@arg PARALLEL: --parallel +global "memory-maps the text list of transactions and parses it in parallel")
+        (@arg DELIMITER: --delimiter +takes_value +global default_value(",") "CSV fields delimiter")
+        (@arg TIMESTAMP_CHECK: --("timestamp-check") +takes_value +global possible_value[off warn error] default_value("warn") "how transactions timestamped before their parents are treated")
+        (@subcommand audit =>
+            (about: "detects the lazy approvals and the parasite chains attached to the old parts of the tangle")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg LAZY_GAP: --("lazy-gap") +takes_value default_value("10") "timestamp gap to the youngest parent above which an approval is lazy")
+            (@arg MIN_CHAIN_SIZE: --("min-chain-size") +takes_value default_value("3") "minimum number of transactions of a reported parasite chain")
+        )
+        (@subcommand balances =>
+            (about: "applies the value transfers and returns the address balances")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg TOP: --top +takes_value default_value("10") "number of the richest addresses printed")
+        )
+        (@subcommand confirmation =>
+            (about: "replays the list of transactions and returns the confirmation latency statistics")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg WEIGHT: --weight +takes_value conflicts_with[TIPS] "cumulative weight confirming a transaction, 10 by default")
+            (@arg TIPS: --tips +takes_value conflicts_with[MILESTONES] "number of approving tips confirming a transaction")
+            (@arg MILESTONES: -m --milestones +takes_value conflicts_with[WEIGHT] "path to a file containing a list of milestones confirming transactions")
+        )
+        (@subcommand conflicts =>
+            (about: "detects the concurrent transactions double-spending from the same address and returns the conflict resolution statistics")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg WEIGHT: --weight +takes_value conflicts_with[MILESTONES] "cumulative weight resolving a conflict, 10 by default")
+            (@arg MILESTONES: -m --milestones +takes_value "path to a file containing a list of milestones resolving conflicts")
+        )
+        (@subcommand convert =>
+            (about: "converts the list of transactions between the formats")
+            (@arg DB_FILE_PATH: +required "path to a file containing a list of transactions")
+            (@arg OUTPUT: +required "path to the output file")
+            (@arg OUTPUT_FORMAT: --("output-format") +takes_value possible_value[text jsonl csv binary] default_value("binary") "format of the converted list of transactions")
+        )
+        (@subcommand dominators =>
+            (about: "returns the transactions every path to transaction 1 of other transactions crosses")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg TOP: --top +takes_value default_value("10") "number of the transactions dominating the most transactions printed")
+        )
+        (@subcommand export =>
+            (about: "exports the computed fields of every transaction as CSV")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg OUTPUT: -o --output +takes_value "path to the output file, the fields are printed if omitted")
+        )
+        (@subcommand generate =>
+            (about: "generates a synthetic list of transactions")
+            (@arg OUTPUT: "path to the output file, the list is printed if omitted")
+            (@arg OUTPUT_FORMAT: --("output-format") +takes_value possible_value[text jsonl csv binary] default_value("text") "format of the generated list of transactions")
+            (@arg TRANSACTIONS: -n --transactions +takes_value default_value("1000") "number of transactions to generate")
+            (@arg RATE: --rate +takes_value default_value("10") "average number of transactions per timestamp")
+            (@arg PARENTS: --parents +takes_value default_value("2") "number of parents selected by every transaction, from 1 to 8")
+            (@arg DELAY: --delay +takes_value default_value("1") "time a transaction needs to become visible")
+            (@arg TIP_SELECTION: --("tip-selection") +takes_value possible_value[uniform walk] default_value("uniform") "tip selection strategy")
+            (@arg ALPHA: --alpha +takes_value default_value("0.5") "cumulative weight bias of the random walk")
+            (@arg WALK_WINDOW: --("walk-window") +takes_value default_value("10") "how far back in time the random walks start")
+            (@arg SEED: --seed +takes_value default_value("0") "random number generator seed")
+        )
+        (@subcommand heaviest_path =>
+            (name: "heaviest-path")
+            (about: "returns the path from transaction 1 to a tip following the heaviest approvers")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+        )
+        (@subcommand layers =>
+            (about: "returns the number of transactions, timestamps range, average in-degree and tips of every depth")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg OUTPUT_FORMAT: --("output-format") +takes_value possible_value[text csv json] default_value("text") "format of the layers report")
+        )
+        (@subcommand lca =>
+            (about: "returns the lowest common ancestors of the transactions")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg TXS: -t --txs +takes_value +multiple +require_delimiter +required "comma-separated transactions, at least 2")
+        )
+        (@subcommand metrics =>
+            (about: "returns the statistics in the Prometheus text format")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg MILESTONES: -m --milestones +takes_value "path to a file containing a list of milestones")
+            (@arg TEXTFILE: --textfile +takes_value "path to the node_exporter textfile, the metrics are printed if omitted")
+        )
+        (@subcommand milestones =>
+            (about: "returns the transactions confirmed by every milestone")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg MILESTONES: -m --milestones +takes_value +required "path to a file containing a list of milestones")
+        )
+        (@subcommand path =>
+            (about: "returns the shortest and the longest paths following the references from one transaction to another")
+            (@arg FROM: +required "the approving transaction")
+            (@arg TO: +required "the approved transaction")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+        )
+        (@subcommand serve =>
+            (about: "serves the statistics of the list of transactions over HTTP")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg MILESTONES: -m --milestones +takes_value "path to a file containing a list of milestones")
+            (@arg BIND: --bind +takes_value default_value("127.0.0.1:8080") "address to listen on")
+        )
+        (@subcommand simulate =>
+            (about: "regrows the list of transactions with the given tip selections and compares the statistics")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+            (@arg TIP_SELECTION: --("tip-selection") +takes_value +multiple +require_delimiter possible_value[uniform walk] default_value("uniform,walk") "comma-separated tip selection strategies")
+            (@arg ALPHA: --alpha +takes_value +multiple +require_delimiter default_value("0.5") "comma-separated cumulative weight biases of the random walk")
+            (@arg PARENTS: --parents +takes_value default_value("2") "number of parents selected by every transaction, from 1 to 8")
+            (@arg DELAY: --delay +takes_value default_value("1") "time a transaction needs to become visible")
+            (@arg WALK_WINDOW: --("walk-window") +takes_value default_value("10") "how far back in time the random walks start")
+            (@arg SEED: --seed +takes_value default_value("0") "random number generator seed")
+        )
+        (@subcommand timestamps =>
+            (about: "returns the transactions timestamped before their parents")
+            (@arg DB_FILE_PATH: "path to a file containing a list of transactions")
+        )
+        (@subcommand watch =>
+            (about: "follows a growing list of transactions and periodically returns relevant statistics")
+            (@arg DB_FILE_PATH: +required "path to a file containing a list of transactions")
+            (@arg INTERVAL: -i --interval +takes_value default_value("1") "statistics reporting interval in seconds")
+            (@arg JSON: --json "report statistics as JSON lines")
+        )
     )
     .get_matches();
 
-    let db_file_path = matches
-        .value_of("DB_FILE_PATH")
-        .unwrap_or(DEFAULT_DB_FILE_PATH);
-
-    let database = std::fs::read_to_string(db_file_path)?;
-
-    let transactions = ledger::read_txs_from_db(&database)?;
-
-    let ledger = Ledger::new(transactions);
-
-    println!("----------- Database -----------");
-    println!("{db_file_path}");
-    println!("--------------------------------");
-    println!();
-
-    println!("{ledger:?}");
-    println!();
-
-    println!("------------ Stats -------------");
-    println!("AVG DAG DEPTH: {}", ledger.avg_dag_depth());
-    println!("AVG TXS PER DEPTH: {}", ledger.avg_txs_per_depth());
-    println!("AVG REF: {}", ledger.avg_ref());
-    println!("---");
-    println!("AVG TXS PER TS: {}", ledger.avg_txs_per_ts());
-    println!("--------------------------------");
-
-    Ok(())
+    match matches.subcommand() {
+        ("audit", Some(matches)) => commands::audit::run(matches),
+        ("balances", Some(matches)) => commands::balances::run(matches),
+        ("confirmation", Some(matches)) => commands::confirmation::run(matches),
+        ("conflicts", Some(matches)) => commands::conflicts::run(matches),
+        ("convert", Some(matches)) => commands::convert::run(matches),
+        ("dominators", Some(matches)) => commands::dominators::run(matches),
+        ("export", Some(matches)) => commands::export::run(matches),
+        ("generate", Some(matches)) => commands::generate::run(matches),
+        ("heaviest-path", Some(matches)) => commands::heaviest_path::run(matches),
+        ("layers", Some(matches)) => commands::layers::run(matches),
+        ("lca", Some(matches)) => commands::lca::run(matches),
+        ("metrics", Some(matches)) => commands::metrics::run(matches),
+        ("milestones", Some(matches)) => commands::milestones::run(matches),
+        ("path", Some(matches)) => commands::path::run(matches),
+        ("serve", Some(matches)) => commands::serve::run(matches),
+        ("simulate", Some(matches)) => commands::simulate::run(matches),
+        ("timestamps", Some(matches)) => commands::timestamps::run(matches),
+        ("watch", Some(matches)) => commands::watch::run(matches),
+        _ => commands::stats::run(&matches),
+    }
 }
@@ -0,0 +1,100 @@
+//! # IOTA ledgerstats metrics
+//!
+//! Renders the ledger statistics in the Prometheus text exposition format,
+//! served by the HTTP API or written as a node_exporter textfile.
+
+use std::fmt::Write;
+
+use crate::ledger::Stats;
+
+/// The content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Returns the statistics in the Prometheus text exposition format.
+pub fn render(stats: &Stats) -> String {
+    let metrics = [
+        (
+            "transactions",
+            "Number of transactions, transaction 1 is not included.",
+            stats.transactions.to_string(),
+        ),
+        (
+            "solid_transactions",
+            "Number of transactions with complete past cones.",
+            stats.solid_transactions.to_string(),
+        ),
+        (
+            "unsolid_transactions",
+            "Number of transactions with incomplete past cones.",
+            stats.unsolid_transactions.to_string(),
+        ),
+        (
+            "orphan_transactions",
+            "Number of transactions referencing parents which are not in the ledger.",
+            stats.orphan_transactions.to_string(),
+        ),
+        (
+            "tips",
+            "Number of transactions without approvers.",
+            stats.tips.to_string(),
+        ),
+        (
+            "avg_dag_depth",
+            "Average depth of the directed acyclic graph.",
+            stats.avg_dag_depth.to_string(),
+        ),
+        (
+            "avg_txs_per_depth",
+            "Average number of transactions per depth.",
+            stats.avg_txs_per_depth.to_string(),
+        ),
+        (
+            "avg_ref",
+            "Average number of in-references per node.",
+            stats.avg_ref.to_string(),
+        ),
+        (
+            "avg_txs_per_ts",
+            "Average number of transactions per timestamp.",
+            stats.avg_txs_per_ts.to_string(),
+        ),
+    ];
+
+    let mut output = String::new();
+
+    for (name, help, value) in metrics {
+        // Writing to a string can not fail
+        let _ = writeln!(output, "# HELP ledgerstats_{name} {help}");
+        let _ = writeln!(output, "# TYPE ledgerstats_{name} gauge");
+        let _ = writeln!(output, "ledgerstats_{name} {value}");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ledger::{Ledger, Transaction, Transactions};
+
+    #[test]
+    fn render_sample_metrics() {
+        let mut transactions = Transactions::new();
+
+        transactions.insert(2, Transaction::new(1, 1, 0));
+        transactions.insert(3, Transaction::new(1, 2, 0));
+
+        let output = render(&Ledger::new(transactions).stats());
+
+        assert!(output.starts_with(
+            "# HELP ledgerstats_transactions Number of transactions, transaction 1 is not included.\n\
+             # TYPE ledgerstats_transactions gauge\n\
+             ledgerstats_transactions 2\n"
+        ));
+        assert!(output.contains("ledgerstats_tips 1\n"));
+        assert!(output.contains("ledgerstats_orphan_transactions 0\n"));
+        assert!(output.contains("ledgerstats_avg_txs_per_ts 2\n"));
+        assert_eq!(output.lines().count(), 27);
+    }
+}
@@ -0,0 +1,171 @@
+//! # IOTA ledgerstats HTTP API
+//!
+//! A local HTTP server exposing the statistics and queries of one loaded ledger as JSON endpoints:
+//!
+//! - `GET /stats`
+//! - `GET /metrics`, in the Prometheus text exposition format
+//! - `GET /tips`
+//! - `GET /transactions/{id}`
+//! - `GET /transactions/{id}/past-cone`
+//! - `GET /transactions/{id}/future-cone`
+
+use std::net::SocketAddr;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::ledger::{Ledger, TxId, TxInfo};
+use crate::metrics;
+
+/// Contains all possible errors of the server module.
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("bind error: {0}")]
+    BindError(String),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Type alias for the server module result.
+pub type Result<T> = std::result::Result<T, ServerError>;
+
+/// A response to an HTTP request.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Response {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response content type.
+    pub content_type: &'static str,
+    /// The response body.
+    pub body: String,
+}
+
+impl Response {
+    /// Creates a new JSON `Response` instance.
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self {
+                status,
+                content_type: "application/json",
+                body,
+            },
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    /// Creates a new JSON `Response` instance with the error message.
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// An HTTP server backed by one loaded ledger.
+pub struct Server {
+    /// The HTTP server.
+    http: tiny_http::Server,
+    /// The served ledger.
+    ledger: Ledger,
+}
+
+impl Server {
+    /// Creates a new `Server` instance listening on the provided address.
+    pub fn bind(addr: &str, ledger: Ledger) -> Result<Self> {
+        let http =
+            tiny_http::Server::http(addr).map_err(|e| ServerError::BindError(e.to_string()))?;
+
+        Ok(Self { http, ledger })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Serves the incoming requests until the server fails.
+    pub fn run(&self) -> Result<()> {
+        for request in self.http.incoming_requests() {
+            let response = route(&self.ledger, request.method().as_str(), request.url());
+
+            let content_type = tiny_http::Header::from_bytes("Content-Type", response.content_type)
+                .expect("the header must be valid");
+
+            request.respond(
+                tiny_http::Response::from_string(response.body)
+                    .with_status_code(response.status)
+                    .with_header(content_type),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Routes the request to the ledger query and returns the response.
+pub fn route(ledger: &Ledger, method: &str, url: &str) -> Response {
+    if method != "GET" {
+        return Response::error(405, "method not allowed");
+    }
+
+    let path = url.split('?').next().unwrap_or_default();
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+
+    match segments.as_slice() {
+        ["stats"] => Response::json(200, &ledger.stats()),
+        ["metrics"] => Response {
+            status: 200,
+            content_type: metrics::CONTENT_TYPE,
+            body: metrics::render(&ledger.stats()),
+        },
+        ["tips"] => Response::json(200, &display_ids(ledger, ledger.tips())),
+        ["transactions", id] => query(ledger, id, |id| {
+            ledger
+                .transaction(id)
+                .map(|info| display_info(ledger, info))
+        }),
+        ["transactions", id, "past-cone"] => query(ledger, id, |id| {
+            ledger.past_cone(id).map(|cone| display_ids(ledger, cone))
+        }),
+        ["transactions", id, "future-cone"] => query(ledger, id, |id| {
+            ledger.future_cone(id).map(|cone| display_ids(ledger, cone))
+        }),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// Returns the transaction ids, or their original hashes in a hashed ledger.
+fn display_ids(ledger: &Ledger, ids: impl IntoIterator<Item = TxId>) -> serde_json::Value {
+    match ledger.hashes() {
+        Some(_) => ids.into_iter().map(|id| ledger.display_id(id)).collect(),
+        None => ids.into_iter().collect(),
+    }
+}
+
+/// Returns the transaction information with the referenced transactions mapped like `display_ids`.
+fn display_info(ledger: &Ledger, info: TxInfo) -> serde_json::Value {
+    let mut value = serde_json::json!(&info);
+
+    value["parents"] = display_ids(ledger, info.parents);
+    value["missing_parents"] = display_ids(ledger, info.missing_parents);
+    value["approvers"] = display_ids(ledger, info.approvers);
+
+    value
+}
+
+/// Runs the query for the transaction id, or its original hash in a hashed ledger.
+fn query<T: Serialize>(ledger: &Ledger, id: &str, query: impl Fn(TxId) -> Option<T>) -> Response {
+    match ledger.resolve_id(id) {
+        Some(id) => match query(id) {
+            Some(value) => Response::json(200, &value),
+            None => Response::error(404, "transaction not found"),
+        },
+        None if ledger.hashes().is_some() => Response::error(404, "transaction not found"),
+        None => Response::error(400, "invalid transaction id"),
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use ledgerstats::ledger::{
+    ConfirmationRule, Confirmations, ConflictResolution, Distribution, Ledger, Transaction,
+    Transactions,
+};
+
+fn sample_ledger() -> Ledger {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(1, 2, 0));
+    transactions.insert(4, Transaction::new(2, 2, 1));
+    transactions.insert(5, Transaction::new(3, 3, 2));
+    transactions.insert(6, Transaction::new(3, 4, 3));
+
+    Ledger::new(transactions)
+}
+
+#[test]
+fn cumulative_weight_confirmation() {
+    let report = sample_ledger().confirmations(ConfirmationRule::CumulativeWeight(3));
+
+    assert_eq!(report.confirmations, Confirmations::from([(2, 1), (3, 3)]));
+    assert_eq!(report.unconfirmed, 3);
+    assert_eq!(report.latencies, Distribution::new([1.0, 3.0]));
+    assert_eq!(report.latencies.mean, 2.0);
+}
+
+#[test]
+fn approving_tips_confirmation() {
+    let report = sample_ledger().confirmations(ConfirmationRule::ApprovingTips(2));
+
+    assert_eq!(report.confirmations, Confirmations::from([(2, 1), (3, 3)]));
+    assert_eq!(report.unconfirmed, 3);
+}
+
+#[test]
+fn single_tip_confirms_whole_past_cone() {
+    let report = sample_ledger().confirmations(ConfirmationRule::ApprovingTips(1));
+
+    assert_eq!(
+        report.confirmations,
+        Confirmations::from([(2, 0), (3, 2), (4, 3)])
+    );
+    assert_eq!(report.unconfirmed, 2);
+}
+
+#[test]
+fn empty_ledger_confirmation() {
+    let report =
+        Ledger::new(Transactions::new()).confirmations(ConfirmationRule::CumulativeWeight(1));
+
+    assert!(report.confirmations.is_empty());
+    assert_eq!(report.unconfirmed, 0);
+    assert_eq!(report.latencies, Distribution::default());
+}
+
+#[test]
+fn double_spend_conflicts() {
+    let transactions = ledgerstats::ledger::read_txs_from_db(
+        "4\n1 1 0 +A:10\n2 2 1 -A:10 +B:10\n2 2 2 -A:10 +C:10\n3 3 3\n",
+    )
+    .unwrap();
+
+    let mut ledger = Ledger::new(transactions);
+    ledger.set_milestones(vec![5]).unwrap();
+
+    let report = ledger.conflicts(ConflictResolution::Milestones);
+
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].address, "A");
+    assert_eq!(report.conflicts[0].winner, Some(3));
+    assert_eq!(report.conflicts[0].resolution_time, Some(1));
+    assert_eq!(report.resolved, 1);
+    assert_eq!(report.orphaned, BTreeSet::from([4]));
+
+    let report = ledger.conflicts(ConflictResolution::CumulativeWeight(10));
+
+    assert_eq!(report.conflicts[0].winner, None);
+    assert_eq!(report.resolved, 0);
+    assert!(report.orphaned.is_empty());
+}
+
+#[test]
+fn unfunded_spend_is_not_a_conflict() {
+    // Transaction 3 spends from A concurrently to its issuance, transaction 4 approves the issuance
+    let transactions = ledgerstats::ledger::read_txs_from_db(
+        "3\n1 1 0 +A:10\n1 1 1 -A:10 +B:10\n2 2 2 -A:10 +C:10\n",
+    )
+    .unwrap();
+
+    let ledger = Ledger::new(transactions);
+    let balances = ledger.balances();
+
+    assert_eq!(balances.balances["A"], 0);
+    assert_eq!(balances.balances["C"], 10);
+    assert!(!balances.balances.contains_key("B"));
+    assert_eq!(balances.rejected, BTreeSet::from([3]));
+
+    let report = ledger.conflicts(ConflictResolution::CumulativeWeight(1));
+
+    assert!(report.conflicts.is_empty());
+}
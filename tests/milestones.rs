@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use ledgerstats::ledger::{
+    self, Confirmations, Ledger, LedgerError, Milestone, Transaction, Transactions,
+};
+
+fn sample_ledger() -> Ledger {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(1, 2, 0));
+    transactions.insert(4, Transaction::new(2, 2, 1));
+    transactions.insert(5, Transaction::new(3, 3, 2));
+    transactions.insert(6, Transaction::new(3, 4, 3));
+
+    Ledger::new(transactions)
+}
+
+#[test]
+fn read_milestones_list() {
+    assert_eq!(ledger::read_milestones("4\n6\n").unwrap(), vec![4, 6]);
+    assert_eq!(ledger::read_milestones("").unwrap(), Vec::<usize>::new());
+}
+
+#[test]
+fn read_broken_milestones_list() {
+    assert!(matches!(
+        ledger::read_milestones("4\n🦀").unwrap_err(),
+        LedgerError::ParseIntError(_)
+    ));
+}
+
+#[test]
+fn set_missing_milestone() {
+    let mut ledger = sample_ledger();
+
+    assert_eq!(
+        ledger.set_milestones(vec![4, 7]).unwrap_err(),
+        LedgerError::MissingTxError(7)
+    );
+}
+
+#[test]
+fn sample_milestones() {
+    let mut ledger = sample_ledger();
+
+    ledger.set_milestones(vec![4, 6]).unwrap();
+
+    assert_eq!(
+        ledger.milestones(),
+        vec![
+            Milestone {
+                index: 1,
+                id: 4,
+                timestamp: 1,
+                interval: None,
+                confirmed: BTreeSet::from([2, 4]),
+            },
+            Milestone {
+                index: 2,
+                id: 6,
+                timestamp: 3,
+                interval: Some(2),
+                confirmed: BTreeSet::from([3, 6]),
+            },
+        ]
+    );
+
+    let report = ledger.milestone_confirmations();
+
+    assert_eq!(
+        report.confirmations,
+        Confirmations::from([(2, 1), (3, 3), (4, 1), (6, 3)])
+    );
+    assert_eq!(report.unconfirmed, 1);
+    assert_eq!(report.latencies.max, 3.0);
+}
+
+#[test]
+fn milestone_inside_previous_cone() {
+    let mut ledger = sample_ledger();
+
+    ledger.set_milestones(vec![6, 4]).unwrap();
+
+    let milestones = ledger.milestones();
+
+    assert_eq!(milestones[0].confirmed, BTreeSet::from([2, 3, 4, 6]));
+    assert_eq!(milestones[1].confirmed, BTreeSet::new());
+    assert_eq!(milestones[1].interval, Some(0));
+}
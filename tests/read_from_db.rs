@@ -1,4 +1,7 @@
-use ledgerstats::ledger::{self, LedgerError, ParseTxError, Transaction, Transactions};
+use ledgerstats::ledger::{
+    self, GeneratorConfig, Ledger, LedgerError, ParseTxError, TipSelection, Transaction,
+    Transactions,
+};
 
 #[test]
 fn read_sample_db() {
@@ -72,3 +75,132 @@ fn read_db_with_transactions_number_mismatch() {
         LedgerError::WrongTxNumberError(5, 2)
     );
 }
+
+#[test]
+fn write_and_read_sample_db() {
+    const DATABASE: &str = include_str!("../database.txt");
+
+    let transactions = ledger::read_txs_from_db(DATABASE).unwrap();
+    let database = ledger::write_txs_to_db(&transactions).unwrap();
+
+    assert_eq!(database, format!("{DATABASE}\n"));
+    assert_eq!(ledger::read_txs_from_db(&database).unwrap(), transactions);
+}
+
+#[test]
+fn write_db_with_missing_transaction() {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(4, Transaction::new(2, 2, 1));
+
+    assert_eq!(
+        ledger::write_txs_to_db(&transactions).unwrap_err(),
+        LedgerError::MissingTxError(3)
+    );
+}
+
+#[test]
+fn write_and_read_generated_db() {
+    let mut config = GeneratorConfig {
+        transactions: 50,
+        ..Default::default()
+    };
+    config.simulation.tip_selection = TipSelection::RandomWalk { alpha: 0.1 };
+
+    let transactions = ledger::generate_txs(&config);
+    let database = ledger::write_txs_to_db(&transactions).unwrap();
+
+    assert_eq!(ledger::read_txs_from_db(&database).unwrap(), transactions);
+}
+
+#[test]
+fn read_partial_db() {
+    const DATABASE: &str = "2\n\
+                            1 1 0\n\
+                            2 5 0";
+
+    let ledger = Ledger::new(ledger::read_txs_from_db(DATABASE).unwrap());
+
+    assert_eq!(ledger.solid_txs_number(), 1);
+    assert_eq!(ledger.unsolid_txs_number(), 1);
+}
+
+#[cfg(feature = "jsonl")]
+#[test]
+fn jsonl_roundtrip_keeps_ids() {
+    let database = std::fs::read_to_string("./database.txt").unwrap();
+    let transactions = ledger::read_txs_from_db(&database).unwrap();
+
+    let jsonl = ledger::write_txs_to_jsonl(&transactions).unwrap();
+
+    assert_eq!(jsonl.lines().count(), 5);
+    assert_eq!(ledger::read_txs_from_jsonl(&jsonl).unwrap(), transactions);
+}
+
+#[test]
+fn csv_roundtrip_keeps_ids() {
+    let database = std::fs::read_to_string("./database.txt").unwrap();
+    let transactions = ledger::read_txs_from_db(&database).unwrap();
+
+    let csv = ledger::write_txs_to_csv(&transactions, b',').unwrap();
+
+    assert!(csv.starts_with("id,left,right,timestamp\n2,1,1,0\n"));
+    assert_eq!(ledger::read_txs_from_csv(&csv, b',').unwrap(), transactions);
+}
+
+#[test]
+fn binary_roundtrip_of_generated_ledger() {
+    let transactions = ledger::generate_txs(&GeneratorConfig::default());
+
+    let bytes = ledger::write_txs_to_binary(&transactions).unwrap();
+    let binary = ledger::BinaryLedger::parse(&bytes).unwrap();
+
+    assert_eq!(binary.len(), transactions.len());
+    assert_eq!(binary.get(0), Some((2, transactions[&2].clone())));
+    assert_eq!(ledger::read_txs_from_binary(&bytes).unwrap(), transactions);
+}
+
+#[test]
+fn read_hashed_db_reports_hashes() {
+    let database = "999\nAB 999 999 0\nCD 999 AB 0\nEF AB AB 1\n12 CD FF 2\n";
+
+    let (transactions, hashes) = ledger::read_hashed_txs_from_db(database).unwrap();
+
+    assert_eq!(transactions.len(), 4);
+    assert_eq!(transactions[&5], Transaction::new(3, 6, 2));
+
+    let mut ledger = Ledger::new(transactions);
+    ledger.set_hashes(hashes);
+
+    assert_eq!(ledger.display_id(1), "999");
+    assert_eq!(ledger.resolve_id("EF"), Some(4));
+    assert_eq!(ledger.resolve_id("4"), None);
+    assert_eq!(ledger.unsolid_txs_number(), 1);
+    assert_eq!(ledger.transaction(3).unwrap().hash, Some("CD".to_owned()));
+    assert!(format!("{ledger:?}").contains("Unsolid:\n12 - FF\n"));
+}
+
+#[test]
+fn add_transaction_to_hashed_ledger() {
+    let database = "999\nAB 999 999 0\nCD AB FF 1\n";
+
+    let (transactions, hashes) = ledger::read_hashed_txs_from_db(database).unwrap();
+
+    let mut ledger = Ledger::new(transactions);
+    ledger.set_hashes(hashes);
+
+    // Id 4 is interned for the missing parent FF
+    assert_eq!(ledger.add_transaction(Transaction::new(2, 2, 2)), Ok(5));
+    assert_eq!(ledger.add_transaction(Transaction::new(5, 3, 3)), Ok(6));
+
+    assert_eq!(ledger.display_id(4), "FF");
+    assert_eq!(ledger.display_id(5), "5");
+    assert_eq!(ledger.transaction(4), None);
+    assert_eq!(ledger.transaction(5).unwrap().approvers, [6].into());
+    assert_eq!(ledger.unsolid_txs_number(), 2);
+    assert_eq!(
+        ledger.unsolid_txs(),
+        [(3, [4].into()), (6, [].into())].into()
+    );
+}
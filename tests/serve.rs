@@ -0,0 +1,177 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+
+use serde_json::{json, Value};
+
+use ledgerstats::ledger::{self, Ledger, Transaction, Transactions};
+use ledgerstats::server::{self, Server};
+
+/// Starts a server on a random loopback port and returns its address.
+fn start_server() -> SocketAddr {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(1, 2, 0));
+    transactions.insert(4, Transaction::new(2, 2, 1));
+    transactions.insert(5, Transaction::new(3, 3, 2));
+    transactions.insert(6, Transaction::new(3, 4, 3));
+
+    let server = Server::bind("127.0.0.1:0", Ledger::new(transactions)).unwrap();
+    let addr = server.local_addr().unwrap();
+
+    thread::spawn(move || server.run());
+
+    addr
+}
+
+/// Sends a request to the server and returns the response status, head and body.
+fn raw_request(addr: SocketAddr, method: &str, path: &str) -> (u16, String, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+
+    (status, head.to_owned(), body.to_owned())
+}
+
+/// Sends a request to the server and returns the response status and JSON body.
+fn request(addr: SocketAddr, method: &str, path: &str) -> (u16, Value) {
+    let (status, head, body) = raw_request(addr, method, path);
+
+    assert!(head.contains("Content-Type: application/json"));
+
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+#[test]
+fn stats_endpoint() {
+    let addr = start_server();
+
+    let (status, body) = request(addr, "GET", "/stats");
+
+    assert_eq!(status, 200);
+    assert_eq!(body["transactions"], 5);
+    assert_eq!(body["avg_txs_per_depth"], 2.5);
+}
+
+#[test]
+fn metrics_endpoint() {
+    let addr = start_server();
+
+    let (status, head, body) = raw_request(addr, "GET", "/metrics");
+
+    assert_eq!(status, 200);
+    assert!(head.contains("Content-Type: text/plain; version=0.0.4"));
+    assert!(body.contains("# TYPE ledgerstats_tips gauge\nledgerstats_tips 2\n"));
+    assert!(body.contains("ledgerstats_transactions 5\n"));
+    assert!(body.contains("ledgerstats_avg_txs_per_depth 2.5\n"));
+}
+
+#[test]
+fn transaction_endpoint() {
+    let addr = start_server();
+
+    let (status, body) = request(addr, "GET", "/transactions/4");
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        json!({
+            "id": 4,
+            "timestamp": 1,
+            "depth": 2,
+            "parents": [2],
+            "missing_parents": [],
+            "approvers": [6],
+        })
+    );
+
+    assert_eq!(request(addr, "GET", "/transactions/7").0, 404);
+    assert_eq!(request(addr, "GET", "/transactions/abc").0, 400);
+}
+
+#[test]
+fn tips_and_cones_endpoints() {
+    let addr = start_server();
+
+    assert_eq!(request(addr, "GET", "/tips"), (200, json!([5, 6])));
+    assert_eq!(
+        request(addr, "GET", "/transactions/4/past-cone"),
+        (200, json!([1, 2]))
+    );
+    assert_eq!(
+        request(addr, "GET", "/transactions/2/future-cone?limit=10"),
+        (200, json!([3, 4, 5, 6]))
+    );
+}
+
+#[test]
+fn hashed_ledger_routes() {
+    let database = "999\nAB 999 999 0\nCD 999 AB 0\nEF AB AB 1\n12 CD EF 2\n";
+    let (transactions, hashes) = ledger::read_hashed_txs_from_db(database).unwrap();
+
+    let mut ledger = Ledger::new(transactions);
+    ledger.set_hashes(hashes);
+
+    let body =
+        |path| serde_json::from_str::<Value>(&server::route(&ledger, "GET", path).body).unwrap();
+
+    assert_eq!(body("/tips"), json!(["12"]));
+    assert_eq!(body("/transactions/EF/past-cone"), json!(["999", "AB"]));
+    assert_eq!(
+        body("/transactions/AB/future-cone"),
+        json!(["CD", "EF", "12"])
+    );
+    assert_eq!(
+        body("/transactions/EF"),
+        json!({
+            "id": 4,
+            "hash": "EF",
+            "timestamp": 1,
+            "depth": 2,
+            "parents": ["AB"],
+            "missing_parents": [],
+            "approvers": ["12"],
+        })
+    );
+    assert_eq!(
+        server::route(&ledger, "GET", "/transactions/4/past-cone").status,
+        404
+    );
+
+    let (transactions, hashes) = ledger::read_hashed_txs_from_db("999\nAB 999 FF 0\n").unwrap();
+
+    let mut ledger = Ledger::new(transactions);
+    ledger.set_hashes(hashes);
+
+    let body =
+        serde_json::from_str::<Value>(&server::route(&ledger, "GET", "/transactions/AB").body);
+
+    assert_eq!(body.unwrap()["missing_parents"], json!(["FF"]));
+}
+
+#[test]
+fn unknown_routes() {
+    let addr = start_server();
+
+    assert_eq!(request(addr, "GET", "/unknown").0, 404);
+    assert_eq!(request(addr, "POST", "/stats").0, 405);
+}
+
+#[test]
+fn routing_without_server() {
+    let ledger = Ledger::new(Transactions::new());
+
+    assert_eq!(server::route(&ledger, "GET", "/tips").body, "[1]");
+    assert_eq!(server::route(&ledger, "GET", "/transactions/1").status, 200);
+}
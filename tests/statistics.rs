@@ -1,4 +1,9 @@
-use ledgerstats::ledger::{Ledger, Transaction, Transactions};
+use std::collections::{BTreeMap, BTreeSet};
+
+use ledgerstats::ledger::{
+    self, AuditConfig, Distribution, Ledger, LedgerError, MissingParents, ParentGapStats,
+    SimulationConfig, Stats, TimestampCheck, Transaction, Transactions,
+};
 
 #[test]
 fn sample_statistics() {
@@ -65,3 +70,323 @@ fn one_more_graph_statistics() {
     assert_eq!(ledger.avg_ref(), 1.7142857);
     assert_eq!(ledger.avg_txs_per_ts(), 2.0);
 }
+
+#[test]
+fn replayed_graph_statistics() {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(1, 2, 0));
+    transactions.insert(4, Transaction::new(2, 2, 1));
+    transactions.insert(5, Transaction::new(3, 3, 2));
+    transactions.insert(6, Transaction::new(3, 4, 3));
+
+    let config = SimulationConfig::default();
+
+    let ledger = Ledger::new(ledger::replay_txs(&transactions, &config));
+
+    assert_eq!(ledger.avg_dag_depth(), 1.8333334);
+    assert_eq!(ledger.avg_txs_per_depth(), 1.25);
+    assert_eq!(ledger.avg_ref(), 1.6666666);
+    assert_eq!(ledger.avg_txs_per_ts(), 1.25);
+}
+
+#[test]
+fn partial_graph_statistics() {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(1, 2, 0));
+    transactions.insert(4, Transaction::new(2, 9, 1));
+    transactions.insert(5, Transaction::new(4, 3, 2));
+
+    let ledger = Ledger::new(transactions);
+
+    assert_eq!(ledger.solid_txs_number(), 2);
+    assert_eq!(ledger.unsolid_txs_number(), 2);
+    assert_eq!(
+        ledger.unsolid_txs(),
+        BTreeMap::from([(4, MissingParents::from([9])), (5, MissingParents::new())])
+    );
+
+    assert_eq!(ledger.avg_dag_depth(), 0.6666667);
+    assert_eq!(ledger.avg_txs_per_depth(), 2.0);
+    assert_eq!(ledger.avg_txs_per_ts(), 4.0 / 3.0);
+}
+
+#[test]
+fn incremental_statistics() {
+    let mut ledger = Ledger::new(Transactions::new());
+
+    assert_eq!(ledger.add_transaction(Transaction::new(1, 1, 0)), Ok(2));
+    assert_eq!(ledger.add_transaction(Transaction::new(1, 2, 0)), Ok(3));
+    assert_eq!(ledger.add_transaction(Transaction::new(2, 2, 1)), Ok(4));
+    assert_eq!(ledger.add_transaction(Transaction::new(3, 3, 2)), Ok(5));
+    assert_eq!(ledger.add_transaction(Transaction::new(3, 4, 3)), Ok(6));
+
+    assert_eq!(ledger.avg_dag_depth(), 1.3333334);
+    assert_eq!(ledger.avg_txs_per_depth(), 2.5);
+    assert_eq!(ledger.avg_ref(), 1.6666666);
+    assert_eq!(ledger.avg_txs_per_ts(), 1.25);
+}
+
+#[test]
+fn incremental_statistics_with_missing_parent() {
+    let mut transactions = Transactions::new();
+
+    transactions.insert(2, Transaction::new(1, 1, 0));
+    transactions.insert(3, Transaction::new(2, 4, 1));
+
+    let mut ledger = Ledger::new(transactions);
+
+    assert_eq!(ledger.unsolid_txs_number(), 1);
+    assert_eq!(ledger.avg_dag_depth(), 0.5);
+
+    assert_eq!(ledger.add_transaction(Transaction::new(2, 2, 1)), Ok(4));
+
+    assert_eq!(ledger.unsolid_txs_number(), 0);
+    assert_eq!(ledger.solid_txs_number(), 3);
+    assert_eq!(ledger.avg_dag_depth(), 1.25);
+    assert_eq!(ledger.avg_txs_per_depth(), 1.5);
+    assert_eq!(ledger.avg_ref(), 1.5);
+}
+
+#[test]
+fn incremental_parent_gaps() {
+    let transactions = [
+        Transaction::new(1, 1, 0),
+        Transaction::new(2, 5, 3),
+        Transaction::new(2, 3, 1),
+        Transaction::with_parents(vec![2, 1, 2], 4),
+        Transaction::new(4, 9, 6),
+    ];
+
+    let mut ledger = Ledger::new(Transactions::new());
+
+    for tx in transactions.iter().cloned() {
+        ledger.add_transaction(tx).unwrap();
+    }
+
+    let batch = Ledger::new((2..).zip(transactions).collect());
+
+    assert_eq!(ledger.stats().parent_gaps, batch.stats().parent_gaps);
+    assert_eq!(
+        ledger.stats().parent_gaps,
+        ParentGapStats::new(&batch.parent_gaps())
+    );
+    assert_eq!(ledger.stats().parent_gaps.depth.count, 7);
+}
+
+#[test]
+fn add_transaction_with_invalid_parent() {
+    let mut ledger = Ledger::new(Transactions::new());
+
+    assert_eq!(
+        ledger.add_transaction(Transaction::new(0, 1, 0)),
+        Err(LedgerError::InvalidParentError(0))
+    );
+    assert_eq!(
+        ledger.add_transaction(Transaction::new(1, 2, 0)),
+        Err(LedgerError::InvalidParentError(2))
+    );
+    assert_eq!(ledger.solid_txs_number(), 0);
+}
+
+#[test]
+fn variable_parents_statistics() {
+    let mut ledger = Ledger::new(Transactions::new());
+
+    assert_eq!(
+        ledger.add_transaction(Transaction::with_parents(vec![1], 0)),
+        Ok(2)
+    );
+    assert_eq!(
+        ledger.add_transaction(Transaction::with_parents(vec![2, 2, 1], 0)),
+        Ok(3)
+    );
+    assert_eq!(
+        ledger.add_transaction(Transaction::with_parents(vec![3, 2, 1, 3], 1)),
+        Ok(4)
+    );
+    assert_eq!(
+        ledger.add_transaction(Transaction::with_parents(vec![], 1)),
+        Err(LedgerError::WrongParentsNumberError(0))
+    );
+    assert_eq!(
+        ledger.add_transaction(Transaction::with_parents(vec![1; 9], 1)),
+        Err(LedgerError::WrongParentsNumberError(9))
+    );
+
+    assert_eq!(ledger.avg_dag_depth(), 0.75);
+    assert_eq!(ledger.avg_txs_per_depth(), 3.0);
+    assert_eq!(ledger.avg_ref(), 2.0);
+    assert_eq!(ledger.avg_txs_per_ts(), 1.5);
+}
+
+#[test]
+fn sample_stats_snapshot() {
+    let mut ledger = Ledger::new(Transactions::new());
+
+    ledger.add_transaction(Transaction::new(1, 1, 0)).unwrap();
+    ledger.add_transaction(Transaction::new(1, 2, 0)).unwrap();
+    ledger.add_transaction(Transaction::new(2, 5, 1)).unwrap();
+
+    assert_eq!(
+        ledger.stats(),
+        Stats {
+            transactions: 3,
+            solid_transactions: 2,
+            unsolid_transactions: 1,
+            orphan_transactions: 1,
+            tips: 2,
+            avg_dag_depth: 0.6666667,
+            avg_txs_per_depth: 2.0,
+            avg_ref: 1.25,
+            avg_txs_per_ts: 1.5,
+            parent_gaps: ParentGapStats {
+                id: Distribution::new([1.0, 2.0, 1.0, 2.0, -1.0]),
+                timestamp: Distribution::new([0.0, 1.0]),
+                depth: Distribution::new([1.0, 1.0, 0.0]),
+            },
+        }
+    );
+}
+
+#[test]
+fn value_transfers_balances() {
+    let transactions = ledger::read_txs_from_db(
+        "5\n1 1 0 +A:10\n2 2 1 -A:4 +B:4\n3 3 2 -B:5 +C:5\n3 2 3 -A:6 +B:3 +C:3\n5 1 4 +C:100\n",
+    )
+    .unwrap();
+
+    let balances = Ledger::new(transactions).balances();
+
+    assert_eq!(
+        balances.balances,
+        BTreeMap::from([
+            ("A".to_owned(), 0),
+            ("B".to_owned(), 7),
+            ("C".to_owned(), 3)
+        ])
+    );
+    assert_eq!(balances.total_supply, 10);
+    assert_eq!(balances.active_addresses, 2);
+    assert_eq!(balances.applied, 3);
+    assert_eq!(balances.rejected, BTreeSet::from([4, 6]));
+}
+
+#[test]
+fn total_supply_overflow() {
+    let transactions =
+        ledger::read_txs_from_db("2\n1 1 0 +A:18446744073709551615\n1 1 0 +B:1\n").unwrap();
+
+    let balances = Ledger::new(transactions).balances();
+
+    assert_eq!(balances.total_supply, u64::MAX);
+    assert_eq!(balances.applied, 1);
+    assert_eq!(balances.rejected, BTreeSet::from([3]));
+}
+
+#[test]
+fn parasite_chain_audit() {
+    let transactions =
+        ledger::read_txs_from_db("6\n1 1 0\n2 2 10\n3 3 20\n2 2 40\n5 2 41\n6 3 42\n").unwrap();
+
+    let report = Ledger::new(transactions).audit(&AuditConfig::default());
+
+    assert_eq!(report.lazy_approvals.len(), 1);
+    assert_eq!(report.lazy_approvals[0].id, 5);
+    assert_eq!(report.parasite_chains.len(), 1);
+    assert_eq!(
+        report.parasite_chains[0].transactions,
+        BTreeSet::from([5, 6, 7])
+    );
+    assert_eq!(report.score, 12.0);
+}
+
+#[test]
+fn timestamp_consistency() {
+    let transactions = ledger::read_txs_from_db("3\n1 1 5\n2 2 3\n2 3 4\n").unwrap();
+    let ledger = Ledger::new(transactions);
+
+    let report = ledger.validate_timestamps(TimestampCheck::Warn).unwrap();
+
+    assert_eq!(report.violations.len(), 2);
+    assert_eq!(report.violating_references, 2);
+    assert_eq!(report.magnitudes.max, 2.0);
+    assert_eq!(
+        ledger
+            .validate_timestamps(TimestampCheck::Error)
+            .unwrap_err(),
+        LedgerError::TimestampViolationError(3, 2, 2)
+    );
+}
+
+#[test]
+fn layers_to_csv() {
+    let transactions = ledger::read_txs_from_db("3\n1 1 0\n1 2 1\n2 3 2\n").unwrap();
+    let layers = Ledger::new(transactions).layers();
+
+    assert_eq!(layers.len(), 3);
+    assert_eq!(
+        ledger::write_layers_to_csv(&layers, b',').unwrap(),
+        "depth,transactions,min_timestamp,max_timestamp,avg_in_degree,tips\n\
+         0,1,,,2.0,0\n\
+         1,2,0,1,1.5,0\n\
+         2,1,2,2,0.0,1\n"
+    );
+}
+
+#[test]
+fn sample_heaviest_path() {
+    let transactions = ledger::read_txs_from_db("4\n1 1 0\n1 1 0\n3 3 1\n2 2 2\n").unwrap();
+    let ledger = Ledger::new(transactions);
+
+    let path = ledger.heaviest_path();
+
+    assert_eq!(path, vec![1, 2, 5]);
+    assert_eq!(ledger.one_hop_fraction(&path), 0.75);
+}
+
+#[test]
+fn shortest_and_longest_paths() {
+    let transactions = ledger::read_txs_from_db("3\n1 1 0\n2 2 1\n3 1 2\n").unwrap();
+    let ledger = Ledger::new(transactions);
+
+    assert_eq!(ledger.shortest_path(4, 1), Some(vec![4, 1]));
+    assert_eq!(ledger.longest_path(4, 1), Some(vec![4, 3, 2, 1]));
+    assert_eq!(ledger.shortest_path(1, 4), None);
+    assert_eq!(ledger.longest_path(1, 4), None);
+}
+
+#[test]
+fn lowest_common_ancestors() {
+    let transactions = ledger::read_txs_from_db("4\n1 1 0\n1 1 0\n2 3 1\n2 3 1\n").unwrap();
+    let ledger = Ledger::new(transactions);
+
+    assert_eq!(
+        ledger.lowest_common_ancestors(&[4, 5]),
+        Some(BTreeSet::from([2, 3]))
+    );
+    assert_eq!(
+        ledger.lowest_common_ancestors(&[2, 3, 4]),
+        Some(BTreeSet::from([1]))
+    );
+    assert_eq!(ledger.lowest_common_ancestors(&[4, 8]), None);
+}
+
+#[test]
+fn dominator_tree() {
+    let transactions = ledger::read_txs_from_db("4\n1 1 0\n2 2 1\n3 3 2\n4 2 3\n").unwrap();
+    let dominators = Ledger::new(transactions).dominators();
+
+    assert_eq!(
+        dominators.idoms,
+        BTreeMap::from([(2, 1), (3, 2), (4, 3), (5, 2)])
+    );
+    assert_eq!(
+        dominators.frontiers,
+        BTreeMap::from([(3, BTreeSet::from([5])), (4, BTreeSet::from([5]))])
+    );
+    assert_eq!(dominators.bottlenecks(), vec![(2, 3), (3, 1)]);
+}
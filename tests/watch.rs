@@ -0,0 +1,76 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+use serde_json::Value;
+
+/// Writes the database to a file unique to the test and returns its path.
+fn write_database(name: &str, database: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "ledgerstats-watch-{name}-{}.txt",
+        std::process::id()
+    ));
+
+    std::fs::write(&path, database).unwrap();
+
+    path
+}
+
+/// Starts the watch mode reporting JSON statistics on the database.
+fn watch(path: &PathBuf, interval: &str) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_ledgerstats"))
+        .args(["watch", "--json", &format!("--interval={interval}")])
+        .arg(path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+fn truncated_last_line() {
+    let path = write_database("truncated", "3\n1 1 0\n1 2 0\n2 ");
+
+    let mut child = watch(&path, "0.1");
+    let mut reports = BufReader::new(child.stdout.take().unwrap()).lines();
+
+    let mut next_report =
+        || -> Value { serde_json::from_str(&reports.next().unwrap().unwrap()).unwrap() };
+
+    // The cut transaction is not reported until its line is complete
+    assert_eq!(next_report()["transactions"], 2);
+
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(b"2 1\n")
+        .unwrap();
+
+    let report = (0..50)
+        .map(|_| next_report())
+        .find(|report| report["transactions"] != 2)
+        .unwrap();
+
+    assert_eq!(report["transactions"], 3);
+    assert_eq!(report["solid_transactions"], 3);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_interval() {
+    let path = write_database("interval", "1\n1 1 0\n");
+
+    for interval in ["0", "-1", "NaN", "inf", "1e30"] {
+        let output = watch(&path, interval).wait_with_output().unwrap();
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("the interval must be"));
+    }
+
+    std::fs::remove_file(path).unwrap();
+}